@@ -1,46 +1,99 @@
-use std::{fs::read_dir, path::Path};
+use std::{collections::BTreeMap, fs::read_dir, path::Path};
 
 use proc_macro::TokenStream;
 
-/// Generates an expression with collects all asset paths in a vector and returns it.
+/// Generates a catalogue of all assets: a module for every folder in the assets folder, with a constant
+/// holding the asset path of every file in it. The constant ALL holds the paths of every asset.
+///
+/// For example, "sounds/waka.ogg" becomes `sounds::WAKA_OGG`. This way, a typo in an asset path or a removed
+/// asset is a compile error.
 #[proc_macro]
-pub fn load_assets(_item: TokenStream) -> TokenStream {
-    let paths = load_asset_paths();
-    let mut expression = "{let mut paths = Vec::new();".to_string();
+pub fn asset_catalogue(_item: TokenStream) -> TokenStream {
+    let folder = load_folder(Path::new("./assets"), "").expect("the assets folder should exist");
+    let mut code = folder.to_code();
 
-    for path in paths {
-        expression += format!("paths.push(\"{path}\");").as_str();
+    code += "/// The paths of all assets\npub const ALL: &[&str] = &[";
+
+    for path in folder.paths() {
+        code += format!("{path:?},").as_str();
     }
 
-    expression += "paths}";
+    code += "];";
 
-    expression.parse().unwrap()
+    code.parse().unwrap()
 }
 
-fn load_asset_paths() -> Vec<String> {
-    load_asset_paths_recursive(Path::new("./assets")).expect("the assets folder should exist")
+/// The files and sub folders of a folder in the assets folder, sorted by name.
+#[derive(Default)]
+struct Folder {
+    /// The asset path of every file, by file name
+    files: BTreeMap<String, String>,
+    folders: BTreeMap<String, Folder>,
 }
 
-fn load_asset_paths_recursive(path: &Path) -> std::io::Result<Vec<String>> {
-    let mut files = vec![];
-
-    if path.is_dir() {
-        for entry in read_dir(path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                files.extend(load_asset_paths_recursive(&path)?);
-            } else {
-                let path_str = path
-                    .to_str()
-                    .unwrap()
-                    .replace('\\', "/")
-                    .replace("./assets/", "")
-                    .to_string();
-                files.push(path_str);
-            }
+impl Folder {
+    fn to_code(&self) -> String {
+        let mut code = String::new();
+
+        for (name, path) in &self.files {
+            code += format!("pub const {}: &str = {path:?};", identifier(name).to_uppercase()).as_str();
         }
+
+        for (name, folder) in &self.folders {
+            code += format!("pub mod {} {{{}}}", identifier(name).to_lowercase(), folder.to_code()).as_str();
+        }
+
+        code
     }
 
-    Ok(files)
+    fn paths(&self) -> Vec<&str> {
+        self.files
+            .values()
+            .map(String::as_str)
+            .chain(self.folders.values().flat_map(Folder::paths))
+            .collect()
+    }
+}
+
+fn load_folder(
+    path: &Path,
+    asset_path: &str,
+) -> std::io::Result<Folder> {
+    let mut folder = Folder::default();
+    let mut identifiers = BTreeMap::new();
+
+    for entry in read_dir(path)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_str().unwrap().to_string();
+        let entry_asset_path = match asset_path {
+            "" => name.clone(),
+            _ => format!("{asset_path}/{name}"),
+        };
+
+        if let Some(other) = identifiers.insert(identifier(&name).to_lowercase(), entry_asset_path.clone()) {
+            panic!("the assets {other} and {entry_asset_path} would get the same name in the asset catalogue, rename one of them")
+        }
+
+        if path.is_dir() {
+            folder.folders.insert(name, load_folder(&path, &entry_asset_path)?);
+        } else {
+            folder.files.insert(name, entry_asset_path);
+        }
+    }
+
+    Ok(folder)
+}
+
+/// Turn a file or folder name into a valid identifier, like "PressStart2P-Regular.ttf" to "PressStart2P_Regular_ttf".
+fn identifier(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    match identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{identifier}"),
+        false => identifier,
+    }
 }
@@ -1,9 +1,7 @@
-use std::{fs::read_dir, path::Path};
-
 use bevy::{asset::LoadState, prelude::*, state::state::FreelyMutableState};
 
-/// Plugin that starts loading all assets in the asset folder for a given state and
-/// automatically switches to another given state when everything is loaded.
+/// Plugin that starts loading the given assets for a given state and automatically switches
+/// to another given state when everything is loaded.
 pub struct AssetPreloadPlugin<
     LoadingState: States + FreelyMutableState,
     NextState: States + FreelyMutableState,
@@ -12,29 +10,15 @@ pub struct AssetPreloadPlugin<
     loading_state: LoadingState,
     /// The state the plugin will switch to when all assets are loaded
     next_state: NextState,
-    /// The path from where the paths to load the assets from originate
-    path_source: PathSource,
+    /// The paths of the assets to load
+    paths: Vec<String>,
 }
 
 impl<LoadingState: States + FreelyMutableState, NextState: States + FreelyMutableState>
     AssetPreloadPlugin<LoadingState, NextState>
 {
-    /// Load all assets directly from the assets folder. This requires access to the file system and will therefore
-    /// not work in WASM.
-    #[allow(dead_code)]
-    pub fn load_from_asset_folder(
-        loading_state: LoadingState,
-        next_state: NextState,
-    ) -> Self {
-        Self {
-            loading_state,
-            next_state,
-            path_source: PathSource::LoadFromFolder,
-        }
-    }
-
-    /// Load all the given assets only. This variant can be used to preload the whole asset folder in a WASM environment. Use the
-    /// load_assets macro to provide a vector of all asset paths which is created at compile time.
+    /// Load all the given assets. Use the asset catalogue (see [crate::assets::ALL]) to preload the whole asset folder,
+    /// which also works in a WASM environment, as the paths are collected at compile time.
     pub fn load_given_paths<S: ToString>(
         loading_state: LoadingState,
         next_state: NextState,
@@ -43,7 +27,7 @@ impl<LoadingState: States + FreelyMutableState, NextState: States + FreelyMutabl
         Self {
             loading_state,
             next_state,
-            path_source: PathSource::GivenPaths(paths.into_iter().map(|s| s.to_string()).collect()),
+            paths: paths.into_iter().map(|s| s.to_string()).collect(),
         }
     }
 }
@@ -58,7 +42,7 @@ impl<LoadingState: States + FreelyMutableState, NextState: States + FreelyMutabl
         app.add_message::<AssetPreloadUpdate>()
             .add_systems(
                 OnEnter(self.loading_state.clone()),
-                start_asset_loading(self.path_source.clone()),
+                start_asset_loading(self.paths.clone()),
             )
             .add_systems(
                 Update,
@@ -69,41 +53,20 @@ impl<LoadingState: States + FreelyMutableState, NextState: States + FreelyMutabl
 }
 
 #[derive(Message)]
-#[allow(dead_code)]
 pub struct AssetPreloadUpdate {
     /// The amount of assets which are already loaded
     pub num_loaded: usize,
     /// The amount of all assets which get currently loaded or are already loaded
     pub num_loading: usize,
+    /// The assets which could not be loaded. If there are any, the next state is never entered.
+    pub failed: Vec<FailedAsset>,
 }
 
-#[derive(Clone)]
-enum PathSource {
-    /// Load all asset paths from the asset folder.
-    LoadFromFolder,
-    /// Use a given list of paths to load the assets
-    GivenPaths(Vec<String>),
-}
-
-impl PathSource {
-    fn load_assets(
-        &self,
-        asset_server: &AssetServer,
-    ) -> Vec<UntypedHandle> {
-        match self {
-            PathSource::LoadFromFolder => {
-                let paths = load_asset_paths();
-                paths
-                    .into_iter()
-                    .map(|p| asset_server.load_untyped(p).untyped())
-                    .collect()
-            }
-            PathSource::GivenPaths(paths) => paths
-                .iter()
-                .map(|p| asset_server.load_untyped(p).untyped())
-                .collect(),
-        }
-    }
+/// An asset which could not be loaded and why.
+#[derive(Clone, Debug)]
+pub struct FailedAsset {
+    pub path: String,
+    pub error: String,
 }
 
 /// Resource that holds handles to all assets in the assets folder. This only exists to ensure
@@ -121,60 +84,43 @@ impl LoadedAssets {
     }
 }
 
-fn start_asset_loading(path_source: PathSource) -> impl Fn(Commands, Res<AssetServer>) {
+fn start_asset_loading(paths: Vec<String>) -> impl Fn(Commands, Res<AssetServer>) {
     move |mut commands: Commands, asset_server: Res<AssetServer>| {
-        let handles = path_source.load_assets(&asset_server);
+        let handles = paths
+            .iter()
+            .map(|p| asset_server.load_untyped(p).untyped())
+            .collect();
         commands.insert_resource(LoadedAssets(handles));
     }
 }
 
-// TODO copied code, fix!
-fn load_asset_paths() -> Vec<String> {
-    load_asset_paths_recursive(Path::new("./assets")).expect("the assets folder should exist")
-}
-
-fn load_asset_paths_recursive(path: &Path) -> std::io::Result<Vec<String>> {
-    let mut files = vec![];
-
-    if path.is_dir() {
-        for entry in read_dir(path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                files.extend(load_asset_paths_recursive(&path)?);
-            } else {
-                let path_str = path
-                    .to_str()
-                    .unwrap()
-                    .replace('\\', "/")
-                    .replace("./assets/", "")
-                    .to_string();
-                files.push(path_str);
-            }
-        }
-    }
-
-    Ok(files)
-}
-
 #[allow(clippy::type_complexity)]
 fn switch_state_when_all_loaded<S: States + FreelyMutableState>(
     followup_state: S
 ) -> impl Fn(Res<AssetServer>, Res<LoadedAssets>, MessageWriter<AssetPreloadUpdate>, ResMut<NextState<S>>)
 {
     move |asset_server, loaded_assets, mut event_writer, mut next_state| {
-        let num_loaded = loaded_assets
-            .iter()
-            .filter(|uh| match asset_server.load_state(uh.id()) {
-                LoadState::Loaded => true,
-                LoadState::Failed(_) => panic!("load failed!"),
-                _ => false,
-            })
-            .count();
+        let mut num_loaded = 0;
+        let mut failed = vec![];
+
+        for handle in loaded_assets.iter() {
+            match asset_server.load_state(handle.id()) {
+                LoadState::Loaded => num_loaded += 1,
+                LoadState::Failed(error) => failed.push(FailedAsset {
+                    path: asset_server
+                        .get_path(handle.id())
+                        .map(|path| path.to_string())
+                        .unwrap_or_else(|| "<unknown>".to_string()),
+                    error: error.to_string(),
+                }),
+                _ => (),
+            }
+        }
 
         event_writer.write(AssetPreloadUpdate {
             num_loaded,
             num_loading: loaded_assets.num_loading_assets(),
+            failed,
         });
 
         if num_loaded == loaded_assets.num_loading_assets() {
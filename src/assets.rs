@@ -0,0 +1,6 @@
+//! The paths of every asset in the assets folder, generated at compile time. Use these constants instead
+//! of writing asset paths by hand, so a typo or a removed asset is a compile error.
+//!
+//! For example, "sounds/waka.ogg" is `assets::sounds::WAKA_OGG`. [ALL] contains every asset path.
+
+load_assets::asset_catalogue!();
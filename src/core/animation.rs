@@ -7,134 +7,250 @@ pub struct AnimationPlugin;
 impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_message::<AnimationChanged>()
+            .add_message::<AnimationFinished>()
             .add_systems(Update, update_entities_with_animations)
         ;
     }
 }
 
+/// Sent when the current animation of an entity was changed to another one.
+#[derive(Message, Clone, Debug)]
+pub struct AnimationChanged {
+    pub entity: Entity,
+    pub from: String,
+    pub to: String,
+}
+
+/// Sent once when a non-repeating animation of an entity has shown its last texture for the full time.
+#[derive(Message, Clone, Debug)]
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub animation: String,
+}
+
 fn update_entities_with_animations(
     time: Res<Time>,
-    mut query: Query<(&Visibility, &mut Sprite, &mut Animations)>,
+    mut changed_writer: MessageWriter<AnimationChanged>,
+    mut finished_writer: MessageWriter<AnimationFinished>,
+    mut query: Query<(Entity, &Visibility, &mut Sprite, &mut Animations)>,
 ) {
     let delta = time.delta();
-    for (visibility, mut sprite, mut animations) in query.iter_mut() {
+    for (entity, visibility, mut sprite, mut animations) in query.iter_mut() {
+        if let Some(from) = animations.changed_from.take() {
+            changed_writer.write(AnimationChanged {
+                entity,
+                from,
+                to: animations.current.clone(),
+            });
+        }
+
         if visibility != Visibility::Hidden && animations.is_running() {
             animations.current_mut().update(delta);
-            sprite.image = animations.current().texture()
+            animations.current().apply_to(&mut sprite);
+
+            if animations.is_finished() && !animations.finish_reported {
+                animations.finish_reported = true;
+                finished_writer.write(AnimationFinished {
+                    entity,
+                    animation: animations.current.clone(),
+                });
+            }
         }
     }
 }
 
 /// Component that describes a running animation of some entity.
 ///
-/// Basically, you provide a vec of image handles and an animation time. Based
-/// on the elapsed time the index of the current texture is determined.
+/// Basically, you provide a sprite sheet (an image and its texture atlas layout), the frames of the sheet
+/// and how long each of them is shown. Based on the elapsed time the current frame is determined.
 ///
-/// The animation can be repeatable or not. If it is not repeatable, the last texture
-/// in the vector is returned forever.
-///
-/// TODO: add the ability to check if an animation changed
+/// The animation can be repeatable or not. If it is not repeatable, the last frame
+/// is shown forever.
 #[derive(Clone)]
 pub enum Animation {
     SingleTexture {
-        texture: Handle<Image>
+        texture: Handle<Image>,
+        /// The frame of the texture to show, if the texture is a sprite sheet
+        atlas: Option<TextureAtlas>,
     },
-    TextureList {
-        current_texture_index: usize,
+    SpriteSheet {
+        texture: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+        /// The index of the current entry of frames, not the index in the atlas
+        current_frame: usize,
         timer: Timer,
         repeating: bool,
-        textures: Vec<Handle<Image>>,
+        /// If a non-repeating animation has shown its last frame for the full time
+        finished: bool,
+        /// The atlas indexes of the frames, in the order they are shown
+        frames: Vec<usize>,
+        /// How long each frame is shown
+        durations: Vec<Duration>,
     },
 }
 
 impl Animation {
-    /// Create an animation from a single texture. This will always return the same handle when get_current_texture is called. No timers or indexes get updated.
+    /// Create an animation from a single texture. This will always show the same image. No timers or indexes get updated.
     ///
     /// Why? To be used in an Animations collection where some states aren't animated (like the eyes of an eaten ghost).
     pub fn from_texture(texture: Handle<Image>) -> Self {
-        Animation::SingleTexture { texture }
+        Animation::SingleTexture { texture, atlas: None }
+    }
+
+    /// Create an animation which always shows the same frame of a sprite sheet.
+    pub fn from_atlas_frame(texture: Handle<Image>, atlas: TextureAtlas) -> Self {
+        Animation::SingleTexture { texture, atlas: Some(atlas) }
     }
 
-    /// Create an animation from an iterator of image handles.
-    pub fn from_textures(duration_secs: f32, repeating: bool, textures: impl IntoIterator<Item=Handle<Image>>) -> Self {
-        let textures = textures
+    /// Create an animation from frames of a sprite sheet. Every frame is shown for the same time.
+    pub fn from_atlas_evenly(
+        duration_secs: f32,
+        repeating: bool,
+        texture: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+        frames: impl IntoIterator<Item=usize>,
+    ) -> Self {
+        let frames = frames
             .into_iter()
             .collect::<Vec<_>>();
-        let texture_display_time = duration_secs / textures.len() as f32;
+        let frame_display_time = Duration::from_secs_f32(duration_secs / frames.len() as f32);
+
+        Self::from_atlas(repeating, texture, layout, frames.into_iter().map(|frame| (frame, frame_display_time)))
+    }
+
+    /// Create an animation from frames of a sprite sheet and how long each of them is shown.
+    pub fn from_atlas(
+        repeating: bool,
+        texture: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+        frames: impl IntoIterator<Item=(usize, Duration)>,
+    ) -> Self {
+        let (frames, durations): (Vec<_>, Vec<_>) = frames.into_iter().unzip();
+        let first_duration = durations.first().copied().unwrap_or_default();
 
-        Animation::TextureList {
-            current_texture_index: 0,
-            timer: Timer::new(Duration::from_secs_f32(texture_display_time), TimerMode::Repeating),
+        Animation::SpriteSheet {
+            texture,
+            layout,
+            current_frame: 0,
+            timer: Timer::new(first_duration, TimerMode::Repeating),
             repeating,
-            textures,
+            finished: false,
+            frames,
+            durations,
         }
     }
 
     /// Update the animation.
     ///
-    /// For texture lists and sprite sheet, this process is mostly the same: The timer for the current sprite gets
-    /// updated with the given delta.
-    /// If the timer finished, increase the current texture index.
-    /// But if the index is at its max, set it to zero if the animation does not repeat.
+    /// The timer for the current frame gets updated with the given delta.
+    /// If the timer finished, advance to the next frame and show it for its duration.
+    /// But if the frame is the last one, start over if the animation repeats.
     ///
     /// If the animation is stopped or it is a single texture animation, do nothing.
     pub fn update(&mut self, delta: Duration) {
-        let (current_texture_index, timer, repeating, num_textures) = match self {
+        let (current_frame, timer, repeating, finished, durations) = match self {
             Animation::SingleTexture { .. } => return,
-            Animation::TextureList { current_texture_index, timer, repeating, textures, .. } => (current_texture_index, timer, repeating, textures.len()),
+            Animation::SpriteSheet { current_frame, timer, repeating, finished, durations, .. } => (current_frame, timer, repeating, finished, durations),
         };
 
         timer.tick(delta);
 
         if timer.just_finished() {
-            let at_last_index = *current_texture_index == num_textures - 1;
-            match (repeating, at_last_index) {
-                (true, true) => *current_texture_index = 0,
-                (_, false) => *current_texture_index += 1,
-                (false, true) => ()
+            let at_last_frame = *current_frame == durations.len() - 1;
+            match (repeating, at_last_frame) {
+                (true, true) => *current_frame = 0,
+                (_, false) => *current_frame += 1,
+                (false, true) => *finished = true
             }
+
+            timer.set_duration(durations[*current_frame]);
         }
     }
 
     pub fn texture(&self) -> Handle<Image> {
         match self {
-            Animation::SingleTexture { texture } => texture.clone(),
-            Animation::TextureList { current_texture_index, textures, .. } => textures.get(*current_texture_index).unwrap().clone(),
+            Animation::SingleTexture { texture, .. } => texture.clone(),
+            Animation::SpriteSheet { texture, .. } => texture.clone(),
         }
     }
 
+    /// Return which part of the texture is currently shown, if the texture is a sprite sheet.
+    pub fn atlas(&self) -> Option<TextureAtlas> {
+        match self {
+            Animation::SingleTexture { atlas, .. } => atlas.clone(),
+            Animation::SpriteSheet { layout, current_frame, frames, .. } => Some(TextureAtlas {
+                layout: layout.clone(),
+                index: frames[*current_frame],
+            }),
+        }
+    }
+
+    /// Return a sprite which shows the current frame.
+    pub fn sprite(&self) -> Sprite {
+        Sprite {
+            image: self.texture(),
+            texture_atlas: self.atlas(),
+            ..default()
+        }
+    }
+
+    /// Return an UI image which shows the current frame.
+    pub fn image_node(&self) -> ImageNode {
+        ImageNode {
+            image: self.texture(),
+            texture_atlas: self.atlas(),
+            ..default()
+        }
+    }
+
+    /// Let the given sprite show the current frame.
+    pub fn apply_to(&self, sprite: &mut Sprite) {
+        sprite.image = self.texture();
+        sprite.texture_atlas = self.atlas();
+    }
+
     /// Rewind the animation back to the start. This means:
     /// - reset the timer
-    /// - set the current texture index to zero
+    /// - go back to the first frame
     pub fn reset(&mut self) {
-        let (current_texture_index, timer) = match self {
+        let (current_frame, timer, finished, durations) = match self {
             Animation::SingleTexture { .. } => return,
-            Animation::TextureList { current_texture_index, timer, .. } => (current_texture_index, timer),
+            Animation::SpriteSheet { current_frame, timer, finished, durations, .. } => (current_frame, timer, finished, durations),
         };
 
+        timer.set_duration(durations[0]);
         timer.reset();
-        *current_texture_index = 0;
-    }
-
-    // Return if the current animation iteration is over
-    // pub fn is_finished(&self) -> bool {
-    //     let (current_texture_index, timer, num_textures) = match self {
-    //         Animation::SingleTexture { .. } => return true,
-    //         Animation::TextureList { current_texture_index, timer, textures, .. } => (current_texture_index, timer, textures.len()),
-    //     };
-    //
-    //     *current_texture_index == num_textures - 1 && timer.just_finished()
-    // }
-
-    // Return if an animation is completely finished, not just the current iteration
-    // pub fn is_completely_finished(&self) -> bool {
-    //     let repeating = match self {
-    //         Animation::SingleTexture { .. } => false,
-    //         Animation::TextureList { repeating, .. } => *repeating,
-    //     };
-    //
-    //     !repeating && self.is_finished()
-    // }
+        *current_frame = 0;
+        *finished = false;
+    }
+
+    /// Continue at the frame and time of the given animation, for example when it is replaced by the same animation
+    /// of another theme. If this animation has fewer frames, it continues at its last one.
+    ///
+    /// If one of the animations is a single texture, nothing is done.
+    pub fn continue_from(&mut self, other: &Animation) {
+        let (
+            Animation::SpriteSheet { current_frame, timer, finished, durations, .. },
+            Animation::SpriteSheet { current_frame: other_frame, timer: other_timer, finished: other_finished, .. },
+        ) = (self, other) else {
+            return;
+        };
+
+        *current_frame = (*other_frame).min(durations.len() - 1);
+        *finished = *other_finished;
+        timer.set_duration(durations[*current_frame]);
+        timer.set_elapsed(other_timer.elapsed().min(durations[*current_frame]));
+    }
+
+    /// Return if the animation is completely over. Repeating animations never finish, a single texture
+    /// is finished as soon as it is shown.
+    pub fn is_finished(&self) -> bool {
+        match self {
+            Animation::SingleTexture { .. } => true,
+            Animation::SpriteSheet { finished, .. } => *finished,
+        }
+    }
 }
 
 /// Component for entities that might have more than one animation.
@@ -143,6 +259,7 @@ impl Animation {
 /// handles must be cloned.
 ///
 /// The animation can be switched at runtime. Every animation is identified by a string.
+/// Changes and finished animations are announced with [AnimationChanged] and [AnimationFinished].
 ///
 /// TODO: We could need an Animations variant with a shared timer between animations.
 ///  The ghosts currently use all the same timer duration, and the animation transition could look
@@ -152,7 +269,11 @@ impl Animation {
 pub struct Animations {
     atlas: HashMap<String, Animation>,
     current: String,
-    running: bool
+    running: bool,
+    /// The animation before the last change, if the change was not announced yet
+    changed_from: Option<String>,
+    /// If the current animation was already announced as finished
+    finish_reported: bool,
 }
 
 impl Animations {
@@ -160,7 +281,9 @@ impl Animations {
         Animations {
             atlas: animations.into_iter().map(|(s, anims)| (s.to_string(), anims)).collect(),
             current: current.to_string(),
-            running: true
+            running: true,
+            changed_from: None,
+            finish_reported: false,
         }
     }
 
@@ -172,6 +295,16 @@ impl Animations {
         self.atlas.get_mut(&self.current).expect("current set animation is not part of the animation atlas")
     }
 
+    /// Return the name of the current animation.
+    pub fn current_name(&self) -> &str {
+        &self.current
+    }
+
+    /// Return if the current animation is over, see [Animation::is_finished].
+    pub fn is_finished(&self) -> bool {
+        self.current().is_finished()
+    }
+
     /// Change the current animation.
     ///
     /// The newly selected animation gets reset in the process (this also checks if the new name is in the atlas).
@@ -183,7 +316,19 @@ impl Animations {
 
         if new_current != self.current {
             self.atlas.get_mut(&new_current).expect("the new selected animation does not exist in the atlas").reset();
-            self.current = new_current;
+            let old_current = std::mem::replace(&mut self.current, new_current);
+            self.changed_from.get_or_insert(old_current);
+            self.finish_reported = false;
+        }
+    }
+
+    /// Replace every animation with the one of the same name from the given animations, for example
+    /// when the theme changed. The current animation, its frame and if the animations are running stay the same.
+    pub fn replace_animations(&mut self, animations: Animations) {
+        let old_atlas = std::mem::replace(&mut self.atlas, animations.atlas);
+
+        if let Some(old_current) = old_atlas.get(&self.current) {
+            self.current_mut().continue_from(old_current);
         }
     }
 
@@ -200,4 +345,98 @@ impl Animations {
     pub fn resume(&mut self) {
         self.running = true
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::core::prelude::*;
+
+    fn animation(repeating: bool) -> Animation {
+        Animation::from_atlas(
+            repeating,
+            Handle::default(),
+            Handle::default(),
+            [(3, Duration::from_millis(100)), (5, Duration::from_millis(200))],
+        )
+    }
+
+    fn shown_frame(animation: &Animation) -> usize {
+        animation.atlas().expect("a sprite sheet animation should have an atlas").index
+    }
+
+    #[test]
+    fn every_frame_is_shown_for_its_own_duration() {
+        let mut animation = animation(false);
+
+        animation.update(Duration::from_millis(99));
+        assert_eq!(shown_frame(&animation), 3);
+
+        animation.update(Duration::from_millis(1));
+        assert_eq!(shown_frame(&animation), 5);
+
+        animation.update(Duration::from_millis(150));
+        assert_eq!(shown_frame(&animation), 5);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn a_non_repeating_animation_finishes_after_its_last_frame_and_keeps_showing_it() {
+        let mut animation = animation(false);
+
+        animation.update(Duration::from_millis(100));
+        animation.update(Duration::from_millis(200));
+        assert!(animation.is_finished());
+        assert_eq!(shown_frame(&animation), 5);
+
+        animation.update(Duration::from_millis(500));
+        assert!(animation.is_finished());
+        assert_eq!(shown_frame(&animation), 5);
+    }
+
+    #[test]
+    fn a_repeating_animation_starts_over_and_never_finishes() {
+        let mut animation = animation(true);
+
+        animation.update(Duration::from_millis(100));
+        animation.update(Duration::from_millis(200));
+        assert_eq!(shown_frame(&animation), 3);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn a_reset_animation_starts_over() {
+        let mut animation = animation(false);
+
+        animation.update(Duration::from_millis(100));
+        animation.update(Duration::from_millis(200));
+        animation.reset();
+        assert_eq!(shown_frame(&animation), 3);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn a_replaced_animation_continues_at_the_same_frame() {
+        let mut animations = Animations::new([("walk", animation(true)), ("stand", animation(false))], "stand");
+        animations.change_animation_to("walk");
+        animations.current_mut().update(Duration::from_millis(150));
+
+        animations.replace_animations(Animations::new([("walk", animation(true)), ("stand", animation(false))], "stand"));
+        assert_eq!(animations.current_name(), "walk");
+        assert_eq!(shown_frame(animations.current()), 5);
+
+        animations.current_mut().update(Duration::from_millis(150));
+        assert_eq!(shown_frame(animations.current()), 3);
+    }
+
+    #[test]
+    fn a_single_texture_is_always_finished() {
+        let mut animation = Animation::from_texture(Handle::default());
+
+        animation.update(Duration::from_millis(100));
+        assert!(animation.is_finished());
+        assert!(animation.atlas().is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::prelude::*;
+
+pub(super) struct AudioSettingsPlugin;
+
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<AudioSettings>()
+            .register_type::<AudioChannel>()
+            .init_resource::<AudioSettings>()
+        ;
+    }
+}
+
+/// The volume of every audio channel and if the game is muted.
+///
+/// All volumes are linear, from 0.0 (silent) to 1.0 (full volume).
+#[derive(Resource, Reflect, Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            master: 1.0,
+            music: 1.0,
+            effects: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Load the settings from the given file. Returns None if the file does not exist or is invalid.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("the audio settings should be serializable");
+        std::fs::write(path, json)
+    }
+
+    pub fn get(&self, channel: AudioChannel) -> f32 {
+        match channel {
+            AudioChannel::Master => self.master,
+            AudioChannel::Music => self.music,
+            AudioChannel::Effects => self.effects,
+        }
+    }
+
+    /// Set the volume of the given channel, clamped between 0.0 and 1.0.
+    pub fn set(&mut self, channel: AudioChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+
+        match channel {
+            AudioChannel::Master => self.master = volume,
+            AudioChannel::Music => self.music = volume,
+            AudioChannel::Effects => self.effects = volume,
+        }
+    }
+
+    /// Return the volume a sound of the given channel should actually play with, with respect
+    /// to the master volume and the mute.
+    pub fn effective_volume(&self, channel: AudioChannel) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+
+        match channel {
+            AudioChannel::Master => self.master,
+            channel => self.master * self.get(channel),
+        }
+    }
+}
+
+/// The channels the volume can be adjusted for. The master volume applies to every other channel.
+#[derive(Reflect, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AudioChannel {
+    Master,
+    /// The background tracks, like the siren
+    Music,
+    /// The sound effects, like the waka
+    Effects,
+}
+
+impl AudioChannel {
+    pub const ALL: [AudioChannel; 3] = [AudioChannel::Master, AudioChannel::Music, AudioChannel::Effects];
+
+    /// The message id of the name of the channel, see [Locale]
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            AudioChannel::Master => "master",
+            AudioChannel::Music => "music",
+            AudioChannel::Effects => "effects",
+        }
+    }
+}
+
+/// The file the audio settings are persisted in. Without this resource, the settings are
+/// neither loaded nor saved, so headless games never touch the settings of the player.
+#[derive(Resource, Deref)]
+pub struct AudioSettingsPath(pub String);
+
+impl Default for AudioSettingsPath {
+    fn default() -> Self {
+        AudioSettingsPath(DEFAULT_AUDIO_SETTINGS_PATH.to_string())
+    }
+}
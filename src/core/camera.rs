@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+pub(super) struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<MazeViewport>()
+            .init_resource::<MazeViewport>()
+        ;
+    }
+}
+
+/// Where the maze is shown on the screen, in UI coordinates.
+///
+/// The UI gets scaled together with the maze, so it can be placed relative to the maze
+/// and looks the same for every window and map size.
+#[derive(Resource, Reflect, Copy, Clone, Default, PartialEq)]
+pub struct MazeViewport {
+    pub maze: Rect,
+}
+
+impl MazeViewport {
+    /// The area above the maze reserved for the UI
+    pub fn above(&self) -> Rect {
+        Rect::new(
+            self.maze.min.x,
+            self.maze.min.y - UI_FIELDS_ABOVE_MAZE * FIELD_SIZE,
+            self.maze.max.x,
+            self.maze.min.y,
+        )
+    }
+
+    /// The area beneath the maze reserved for the UI
+    pub fn below(&self) -> Rect {
+        Rect::new(
+            self.maze.min.x,
+            self.maze.max.y,
+            self.maze.max.x,
+            self.maze.max.y + UI_FIELDS_BELOW_MAZE * FIELD_SIZE,
+        )
+    }
+}
+
+/// Return the area which must be visible: the maze and the UI above and beneath it, in world coordinates.
+pub fn visible_area(map: &Map) -> Rect {
+    let bounds = map.bounds();
+
+    Rect::new(
+        bounds.min.x,
+        bounds.min.y - UI_FIELDS_BELOW_MAZE * FIELD_SIZE,
+        bounds.max.x,
+        bounds.max.y + UI_FIELDS_ABOVE_MAZE * FIELD_SIZE,
+    )
+}
+
+/// How much of the available space the maze must still fill when it is scaled by a whole number.
+/// If it would get smaller, it is scaled to fill the whole window instead. Otherwise, the default window
+/// (one texture pixel would be about 1.2 screen pixels) would show the maze a fifth smaller than necessary.
+const MIN_INTEGER_SCALE_SHARE: f32 = 0.9;
+
+/// Return how many physical pixels one world unit takes on a window of the given (physical) size.
+///
+/// If possible, one texture pixel is scaled to a whole number of screen pixels, so the pixel art
+/// stays sharp. Windows too small for that, or where this would leave too much space unused, get a fractional scale.
+pub fn pixels_per_world_unit(
+    window_size: Vec2,
+    map: &Map,
+) -> f32 {
+    let fit = (window_size / visible_area(map).size()).min_element();
+    let world_units_per_texture_pixel = FIELD_SIZE / TEXTURE_FIELD_SIZE;
+    let fractional_scale = fit * world_units_per_texture_pixel;
+    let integer_scale = fractional_scale.floor();
+
+    if integer_scale >= 1.0 && integer_scale >= fractional_scale * MIN_INTEGER_SCALE_SHARE {
+        integer_scale / world_units_per_texture_pixel
+    } else {
+        fit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::core::camera::MIN_INTEGER_SCALE_SHARE;
+    use crate::core::prelude::*;
+
+    /// The size of the arcade maze
+    fn arcade_map() -> Map {
+        Map {
+            width: 28,
+            height: 31,
+        }
+    }
+
+    /// The window size which shows the arcade maze with the given number of screen pixels per texture pixel
+    fn window_for_integer_scale(scale: f32) -> Vec2 {
+        visible_area(&arcade_map()).size() / FIELD_SIZE * TEXTURE_FIELD_SIZE * scale
+    }
+
+    #[test]
+    fn the_bounds_cover_every_tile() {
+        let map = Map { width: 2, height: 3 };
+        let bounds = map.bounds();
+
+        assert_eq!(bounds, Rect::new(-FIELD_SIZE / 2.0, FIELD_SIZE / 2.0, FIELD_SIZE * 1.5, FIELD_SIZE * 3.5));
+        assert_eq!(bounds.size(), Vec2::new(2.0, 3.0) * FIELD_SIZE);
+
+        for pos in [Pos::new(0, 1), Pos::new(1, 1), Pos::new(0, 3), Pos::new(1, 3)] {
+            assert!(bounds.contains(pos.to_vec3(0.0).truncate()), "{pos:?} is outside of the bounds");
+        }
+    }
+
+    #[test]
+    fn the_rows_start_at_one() {
+        let map = Map { width: 2, height: 3 };
+
+        assert!(map.contains(&Pos::new(0, 1)));
+        assert!(map.contains(&Pos::new(1, 3)));
+        assert!(!map.contains(&Pos::new(0, 0)));
+        assert!(!map.contains(&Pos::new(0, 4)));
+        assert!(!map.contains(&Pos::new(2, 1)));
+        assert!(!map.contains(&Pos::new(-1, 1)));
+    }
+
+    #[test]
+    fn the_visible_area_contains_the_ui_above_and_beneath_the_maze() {
+        let map = arcade_map();
+        let bounds = map.bounds();
+        let area = visible_area(&map);
+
+        assert_eq!(area.min.x, bounds.min.x);
+        assert_eq!(area.max.x, bounds.max.x);
+        assert_eq!(area.min.y, bounds.min.y - UI_FIELDS_BELOW_MAZE * FIELD_SIZE);
+        assert_eq!(area.max.y, bounds.max.y + UI_FIELDS_ABOVE_MAZE * FIELD_SIZE);
+    }
+
+    #[test]
+    fn a_texture_pixel_takes_whole_screen_pixels_if_the_window_fits_it() {
+        let map = arcade_map();
+        let texture_pixels_per_world_unit = TEXTURE_FIELD_SIZE / FIELD_SIZE;
+
+        for scale in [1.0, 2.0, 3.0] {
+            for window in [window_for_integer_scale(scale) * 1.01, window_for_integer_scale(scale) * 1.05] {
+                let pixels = pixels_per_world_unit(window, &map);
+
+                assert!((pixels - scale * texture_pixels_per_world_unit).abs() < 0.0001, "{pixels} for scale {scale}");
+            }
+        }
+    }
+
+    #[test]
+    fn the_whole_window_is_used_if_a_whole_number_scale_leaves_too_much_space() {
+        let map = arcade_map();
+        let window = window_for_integer_scale(2.0) / MIN_INTEGER_SCALE_SHARE * 1.01;
+        let fit = (window / visible_area(&map).size()).min_element();
+
+        assert_eq!(pixels_per_world_unit(window, &map), fit);
+    }
+
+    #[test]
+    fn small_windows_get_a_fractional_scale() {
+        let map = arcade_map();
+        let window = window_for_integer_scale(0.5);
+
+        assert_eq!(pixels_per_world_unit(window, &map), (window / visible_area(&map).size()).min_element());
+    }
+
+    #[test]
+    fn the_default_window_shows_the_whole_maze_as_large_as_possible() {
+        let map = arcade_map();
+        let window = Vec2::new(WINDOW_WIDTH as f32, WINDOW_HEIGHT as f32);
+        let shown = visible_area(&map).size() * pixels_per_world_unit(window, &map);
+
+        assert!(shown.x <= window.x + 0.01 && shown.y <= window.y + 0.01);
+        assert!(shown.y >= window.y * MIN_INTEGER_SCALE_SHARE);
+    }
+}
@@ -1,10 +1,9 @@
 use bevy::prelude::*;
+use crate::assets;
 
 pub const WINDOW_WIDTH: u32 = 1280;
 pub const WINDOW_HEIGHT: u32 = 720;
 
-pub const NUM_GHOST_TYPES: usize = 4;
-
 pub const POINTS_PER_DOT: usize = 10;
 pub const POINTS_PER_ENERGIZER: usize = 50;
 pub const POINTS_PER_GHOST: usize = 200;
@@ -20,9 +19,17 @@ pub const ENERGIZER_Z: f32 = 50.0;
 pub const DOT_Z: f32 = 40.0;
 pub const FRUIT_Z: f32 = 30.0;
 
-pub const FONT: &str = "fonts/PressStart2P-Regular.ttf";
+pub const FONT: &str = assets::fonts::PRESSSTART2P_REGULAR_TTF;
+/// Used for every text with characters the other fonts cannot display
+pub const FALLBACK_FONT: &str = assets::fonts::DEJAVUSANS_TTF;
 
 pub const FIELD_SIZE: f32 = 18.5;
+/// The size of a field in the textures, in pixels
+pub const TEXTURE_FIELD_SIZE: f32 = 16.0;
+/// How many fields above the maze are reserved for the UI
+pub const UI_FIELDS_ABOVE_MAZE: f32 = 3.0;
+/// How many fields beneath the maze are reserved for the UI
+pub const UI_FIELDS_BELOW_MAZE: f32 = 3.0;
 pub const FIELD_DIMENSION: Vec2 = Vec2::splat(FIELD_SIZE);
 pub const WALL_DIMENSION: f32 = FIELD_SIZE;
 pub const PACMAN_DIMENSION: f32 = FIELD_SIZE + FIELD_SIZE * 0.6;
@@ -34,5 +41,14 @@ pub const GHOST_DIMENSION: f32 = PACMAN_DIMENSION;
 
 pub const PACMAN_BASE_SPEED: f32 = FIELD_SIZE * 9.0;
 pub const GHOST_BASE_SPEED: f32 = PACMAN_BASE_SPEED;
-
-pub const MAP_SCENE_PATH: &str = "maps/map.scn.ron";
+/// How many ghosts fit into the ghost house: one in front of the entrance and nine inside
+pub const MAX_GHOSTS: usize = 10;
+
+pub const MAP_SCENE_PATH: &str = assets::maps::MAP_SCN_RON;
+pub const DEFAULT_RULESET_PATH: &str = assets::rulesets::ARCADE_RULESET_JSON;
+pub const DEFAULT_THEME_PATH: &str = assets::themes::CLASSIC_THEME_RON;
+pub const DEFAULT_LOCALE_PATH: &str = assets::locales::EN_LOCALE_JSON;
+/// The file the audio settings of the player are saved in, relative to the working directory
+pub const DEFAULT_AUDIO_SETTINGS_PATH: &str = "audio_settings.json";
+/// The file a game in progress is saved in when the game is quit, relative to the working directory
+pub const DEFAULT_SAVE_GAME_PATH: &str = "save_game.json";
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use pad::direction::Direction;
 use pad::direction::Direction::*;
 use crate::core::prelude::*;
 
 /// The direction some entity is currently moving to
-#[derive(Component, Reflect, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum Dir {
     #[default]
     Right,
@@ -3,6 +3,7 @@ use std::time::Duration;
 use bevy::prelude::*;
 #[cfg(debug_assertions)]
 use bevy_inspector_egui::quick::ResourceInspectorPlugin;
+use serde::{Deserialize, Serialize};
 
 use crate::core::prelude::*;
 
@@ -19,6 +20,7 @@ impl Plugin for EdiblesPlugin {
             .register_type::<EatenDots>()
             .register_type::<Fruit>()
             .register_type::<FruitDespawnTimer>()
+            .register_type::<WanderingFruit>()
             .register_type::<Energizers>()
             .register_type::<Energizer>()
             .register_type::<EnergizerOver>()
@@ -84,7 +86,7 @@ impl EatenDots {
 }
 
 /// Fruit which can be eaten for bonus points
-#[derive(Component, Reflect, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub enum Fruit {
     #[default]
     Cherry,
@@ -102,28 +104,107 @@ pub enum Fruit {
 pub struct FruitDespawnTimer(Timer);
 
 impl FruitDespawnTimer {
-    pub fn new() -> Self {
-        FruitDespawnTimer(Timer::new(Duration::from_secs_f32(9.5), TimerMode::Once))
+    pub fn new(seconds: f32) -> Self {
+        FruitDespawnTimer(Timer::new(Duration::from_secs_f32(seconds), TimerMode::Once))
     }
 }
 
-pub fn get_texture_for_fruit(
-    fruit: &Fruit,
-    asset_server: &AssetServer,
-) -> Handle<Image> {
-    asset_server.load(format!(
-        "textures/fruits/{}.png",
-        match fruit {
-            Cherry => "cherry",
-            Strawberry => "strawberry",
-            Peach => "peach",
-            Apple => "apple",
-            Grapes => "grapes",
-            Galaxian => "galaxian",
-            Bell => "bell",
-            Key => "key",
+/// Tells how fruits appear in the maze.
+#[derive(Reflect, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FruitMode {
+    /// Like in the arcade: the fruit appears on the fruit spawn and stays there until its timer runs out.
+    #[default]
+    Static,
+    /// Like in Ms. Pac-Man: the fruit enters the maze through a random tunnel, bounces to the ghost house and
+    /// leaves the maze through another tunnel. After level 7, the fruit is chosen randomly.
+    Wandering,
+}
+
+/// A fruit which bounces along a path through the maze. The fruit is despawned when it reaches
+/// the end of its path.
+#[derive(Component, Reflect)]
+pub struct WanderingFruit {
+    path: Vec<Pos>,
+    next_index: usize,
+    /// The coordinates on the path, without the bouncing applied
+    coordinates: Vec3,
+    bounce_time: f32,
+}
+
+impl WanderingFruit {
+    pub fn new(path: Vec<Pos>) -> Self {
+        let coordinates = path
+            .first()
+            .expect("the path of a wandering fruit must not be empty")
+            .to_vec3(FRUIT_Z);
+
+        WanderingFruit {
+            path,
+            next_index: 1,
+            coordinates,
+            bounce_time: 0.0,
         }
-    ))
+    }
+
+    /// Move the fruit along its path by the given distance.
+    ///
+    /// If the next tile on the path is not a direct neighbour (because the path leads through a tunnel),
+    /// the fruit jumps directly to it.
+    pub fn advance(
+        &mut self,
+        distance: f32,
+    ) {
+        self.bounce_time += distance / FIELD_SIZE;
+        let mut remaining = distance;
+
+        while remaining > 0.0 && !self.reached_end() {
+            let current = Pos::from_vec3(self.coordinates);
+            let next = self.path[self.next_index];
+            let next_coordinates = next.to_vec3(FRUIT_Z);
+
+            if (current.x() - next.x()).abs() + (current.y() - next.y()).abs() > 1 {
+                self.coordinates = next_coordinates;
+                self.next_index += 1;
+                continue;
+            }
+
+            let to_next = next_coordinates - self.coordinates;
+            let length = to_next.length();
+
+            if length <= remaining {
+                self.coordinates = next_coordinates;
+                self.next_index += 1;
+                remaining -= length;
+            } else {
+                self.coordinates += to_next / length * remaining;
+                remaining = 0.0;
+            }
+        }
+    }
+
+    pub fn reached_end(&self) -> bool {
+        self.next_index >= self.path.len()
+    }
+
+    /// The coordinates the fruit is displayed at, which includes the bouncing.
+    pub fn displayed_coordinates(&self) -> Vec3 {
+        let bounce = (self.bounce_time * std::f32::consts::PI).sin().abs() * FIELD_SIZE * 0.3;
+        self.coordinates + Vec3::new(0.0, bounce, 0.0)
+    }
+}
+
+/// Return the name of the theme sprite of the given fruit.
+pub fn get_sprite_name_for_fruit(fruit: &Fruit) -> &'static str {
+    match fruit {
+        Cherry => "fruit_cherry",
+        Strawberry => "fruit_strawberry",
+        Peach => "fruit_peach",
+        Apple => "fruit_apple",
+        Grapes => "fruit_grapes",
+        Galaxian => "fruit_galaxian",
+        Bell => "fruit_bell",
+        Key => "fruit_key",
+    }
 }
 
 /// Parent component for all energizer (for organization only)
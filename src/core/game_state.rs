@@ -1,3 +1,4 @@
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::prelude::*;
 use crate::core::prelude::*;
 
@@ -7,10 +8,25 @@ impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<GameState>()
+            .init_schedule(CleanUpGame)
+            .add_systems(
+                OnExit(Game(GameOver)),
+                clean_up_game,
+            )
         ;
     }
 }
 
+/// Schedule which removes everything a finished game left behind (like the UI, the edibles and the actors) and
+/// resets the score, lives, level and so on. Runs when a new game starts after a game over, and when the
+/// maze is rebuilt in the middle of a game.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CleanUpGame;
+
+pub fn clean_up_game(world: &mut World) {
+    world.run_schedule(CleanUpGame);
+}
+
 /// The states of the games state machine.
 #[derive(States, Reflect, Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum GameState {
@@ -0,0 +1,277 @@
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+pub(super) struct GhostBehaviourPlugin;
+
+impl Plugin for GhostBehaviourPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<SpeedProfile>()
+            .init_resource::<GhostBehaviours>()
+        ;
+    }
+}
+
+/// Registry of everything that makes a ghost type unique.
+///
+/// The game never matches on specific ghosts. It looks up the behaviour of a ghost here instead, so
+/// new ghosts can be added by registering a behaviour for them (typically a [Ghost::Custom]).
+/// By default, the four ghosts of the arcade game are registered.
+#[derive(Resource, Clone)]
+pub struct GhostBehaviours {
+    behaviours: Vec<(Ghost, GhostBehaviour)>,
+}
+
+impl GhostBehaviours {
+    /// Register the behaviour for the given ghost. An already registered behaviour gets replaced.
+    pub fn register(&mut self, ghost: Ghost, behaviour: GhostBehaviour) {
+        match self.behaviours.iter_mut().find(|(g, _)| *g == ghost) {
+            Some((_, b)) => *b = behaviour,
+            None => self.behaviours.push((ghost, behaviour)),
+        }
+    }
+
+    pub fn get(&self, ghost: &Ghost) -> &GhostBehaviour {
+        self.behaviours
+            .iter()
+            .find(|(g, _)| g == ghost)
+            .map(|(_, b)| b)
+            .unwrap_or_else(|| panic!("no behaviour registered for {ghost:?}"))
+    }
+
+    /// Return all registered ghosts, ordered by their release priority.
+    pub fn by_release_priority(&self) -> Vec<Ghost> {
+        let ghosts = self.behaviours.iter().map(|(g, _)| *g).collect::<Vec<_>>();
+        self.sort_by_release_priority(&ghosts)
+    }
+
+    /// Return the given ghosts ordered by their release priority. Ghosts with the same
+    /// priority (like duplicates) keep their order.
+    pub fn sort_by_release_priority(&self, ghosts: &[Ghost]) -> Vec<Ghost> {
+        let mut ghosts = ghosts.to_vec();
+        ghosts.sort_by_key(|g| self.get(g).release_priority);
+        ghosts
+    }
+}
+
+impl Default for GhostBehaviours {
+    fn default() -> Self {
+        GhostBehaviours {
+            behaviours: vec![
+                (Blinky, GhostBehaviour {
+                    chase_target: blinky_chase_target,
+                    scatter_corner: Blinky,
+                    release_priority: 0,
+                    dot_limits: vec![0],
+                    global_dot_limit: 0,
+                    speed_profile: SpeedProfile::Elroy,
+                    textures: [
+                        "blinky_up",
+                        "blinky_down",
+                        "blinky_left",
+                        "blinky_right",
+                    ],
+                    z: BLINKY_Z,
+                    color: Color::srgb(1.0, 0.0, 0.0),
+                    draw_chase_helpers: draw_no_chase_helpers,
+                }),
+                (Pinky, GhostBehaviour {
+                    chase_target: pinky_chase_target,
+                    scatter_corner: Pinky,
+                    release_priority: 1,
+                    dot_limits: vec![0],
+                    global_dot_limit: 7,
+                    speed_profile: SpeedProfile::Normal,
+                    textures: [
+                        "pinky_up",
+                        "pinky_down",
+                        "pinky_left",
+                        "pinky_right",
+                    ],
+                    z: PINKY_Z,
+                    color: Color::srgb(1.0, 0.72, 1.0),
+                    draw_chase_helpers: draw_pinky_chase_helpers,
+                }),
+                (Inky, GhostBehaviour {
+                    chase_target: inky_chase_target,
+                    scatter_corner: Inky,
+                    release_priority: 2,
+                    dot_limits: vec![30, 0],
+                    global_dot_limit: 17,
+                    speed_profile: SpeedProfile::Normal,
+                    textures: [
+                        "inky_up",
+                        "inky_down",
+                        "inky_left",
+                        "inky_right",
+                    ],
+                    z: INKY_Z,
+                    color: Color::srgb(0.0, 1.0, 1.0),
+                    draw_chase_helpers: draw_inky_chase_helpers,
+                }),
+                (Clyde, GhostBehaviour {
+                    chase_target: clyde_chase_target,
+                    scatter_corner: Clyde,
+                    release_priority: 3,
+                    dot_limits: vec![60, 50, 0],
+                    global_dot_limit: 32,
+                    speed_profile: SpeedProfile::Normal,
+                    textures: [
+                        "clyde_up",
+                        "clyde_down",
+                        "clyde_left",
+                        "clyde_right",
+                    ],
+                    z: CLYDE_Z,
+                    color: Color::srgb(1.0, 0.72, 0.32),
+                    draw_chase_helpers: draw_clyde_chase_helpers,
+                }),
+            ]
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GhostBehaviour {
+    /// Calculates the position the ghost moves to when in chase mode.
+    pub chase_target: fn(&ChaseContext) -> Pos,
+    /// The ghost whose [GhostCorner] in the map is the scatter target
+    pub scatter_corner: Ghost,
+    /// Ghosts with a lower value get the spawn slots nearer to the entrance and leave the ghost house first
+    pub release_priority: usize,
+    /// How many dots pacman must eat before the ghost can leave the house, starting at level 1.
+    /// The last value is used for all following levels.
+    pub dot_limits: Vec<usize>,
+    /// How many dots pacman must eat after he died before the ghost can leave the house
+    pub global_dot_limit: usize,
+    pub speed_profile: SpeedProfile,
+    /// The names of the theme sprites for the normal animations when moving up, down, left and right
+    pub textures: [&'static str; 4],
+    pub z: f32,
+    /// The color of the ghost in the debug overlay
+    pub color: Color,
+    /// Draws everything the chase target depends on in the debug overlay, like the line through blinky for inky.
+    pub draw_chase_helpers: fn(&ChaseContext, &mut Gizmos, Color),
+}
+
+impl GhostBehaviour {
+    pub fn dot_limit_for(&self, level: &Level) -> usize {
+        self.dot_limits
+            .get(level.saturating_sub(1))
+            .or(self.dot_limits.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// How the speed of a ghost changes during a level.
+#[derive(Reflect, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SpeedProfile {
+    /// The speed only depends on the level, the ghost state and if the ghost is in a tunnel
+    #[default]
+    Normal,
+    /// Like normal, but the ghost gets faster when only a few dots are left (see the elroy specs of a level)
+    Elroy,
+}
+
+/// Everything a chase target function might need to know.
+pub struct ChaseContext<'a> {
+    pub ghost: Ghost,
+    pub ghost_position: Pos,
+    pub pacman_position: Pos,
+    pub pacman_direction: Dir,
+    pub scatter_position: Pos,
+    /// The positions of all ghosts in the maze, including the current one
+    pub ghost_positions: &'a [(Ghost, Pos)],
+}
+
+impl ChaseContext<'_> {
+    /// Return the position of the nearest ghost of the given type, if one is in the maze.
+    pub fn position_of(&self, ghost: Ghost) -> Option<Pos> {
+        self.ghost_positions
+            .iter()
+            .filter(|(g, _)| *g == ghost)
+            .map(|(_, pos)| *pos)
+            .min_by(|a, b| self.ghost_position.distance(a).total_cmp(&self.ghost_position.distance(b)))
+    }
+}
+
+/// Blinky just follows pacman.
+pub fn blinky_chase_target(ctx: &ChaseContext) -> Pos {
+    ctx.pacman_position
+}
+
+/// Pinky targets the field 4 fields in pacmans direction.
+pub fn pinky_chase_target(ctx: &ChaseContext) -> Pos {
+    ctx.pacman_position.position_in_direction(ctx.pacman_direction, 4)
+}
+
+/// Inky is moving to a field calculated by using pacmans and blinkys position.
+///
+/// 1. You take a field pacman is facing with two fields distance
+/// 2. You shoot a line from blinkys position trough this field
+/// 3. You double this distance. The field this line is ending on is inkys target.
+///
+/// Without a blinky in the maze, inky uses his own position instead.
+pub fn inky_chase_target(ctx: &ChaseContext) -> Pos {
+    let blinky_position = ctx.position_of(Blinky).unwrap_or(ctx.ghost_position);
+    let position_pacman_is_facing = ctx.pacman_position.position_in_direction(ctx.pacman_direction, 2);
+    let x_diff = position_pacman_is_facing.x() - blinky_position.x();
+    let y_diff = position_pacman_is_facing.y() - blinky_position.y();
+    Pos::new(
+        blinky_position.x() + 2 * x_diff,
+        blinky_position.y() + 2 * y_diff,
+    )
+}
+
+/// Clyde follows pacman, but flees to his scatter corner when he gets closer than 8 fields.
+pub fn clyde_chase_target(ctx: &ChaseContext) -> Pos {
+    if ctx.ghost_position.distance(&ctx.pacman_position) < 8.0 {
+        ctx.scatter_position
+    } else {
+        ctx.pacman_position
+    }
+}
+
+/// For ghosts whose chase target speaks for itself.
+pub fn draw_no_chase_helpers(
+    _ctx: &ChaseContext,
+    _gizmos: &mut Gizmos,
+    _color: Color,
+) {}
+
+/// Draw the line from pacman to the tile 4 fields ahead of him.
+pub fn draw_pinky_chase_helpers(
+    ctx: &ChaseContext,
+    gizmos: &mut Gizmos,
+    color: Color,
+) {
+    gizmos.line_2d(tile_center(ctx.pacman_position), tile_center(pinky_chase_target(ctx)), color);
+}
+
+/// Draw the line from blinky through the tile 2 fields ahead of pacman to the target.
+pub fn draw_inky_chase_helpers(
+    ctx: &ChaseContext,
+    gizmos: &mut Gizmos,
+    color: Color,
+) {
+    let blinky_position = ctx.position_of(Blinky).unwrap_or(ctx.ghost_position);
+    let position_pacman_is_facing = ctx.pacman_position.position_in_direction(ctx.pacman_direction, 2);
+
+    gizmos.line_2d(tile_center(blinky_position), tile_center(inky_chase_target(ctx)), color);
+    gizmos.circle_2d(tile_center(position_pacman_is_facing), FIELD_SIZE / 4.0, color);
+}
+
+/// Draw the radius around clyde in which he flees to his corner.
+pub fn draw_clyde_chase_helpers(
+    ctx: &ChaseContext,
+    gizmos: &mut Gizmos,
+    color: Color,
+) {
+    gizmos.circle_2d(tile_center(ctx.ghost_position), FIELD_SIZE * 8.0, color);
+}
+
+fn tile_center(pos: Pos) -> Vec2 {
+    pos.to_vec3(0.0).truncate()
+}
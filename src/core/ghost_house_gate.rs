@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use std::time::Duration;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use ActiveCounter::*;
 
@@ -19,34 +20,38 @@ use crate::core::prelude::*;
 /// If the per ghost counter is active, a ghost can leave if its personal limit is reached. Only
 /// the counter from the currently waiting ghost is incremented.
 ///
-/// The order of preference for ghosts is Blinky, Pinky, Inky and Clyde. Blinky and Pinky can always
+/// Ghosts are identified by their [GhostSlot], so the same ghost type can be in the house multiple times.
+/// The order of preference is the slot order, which follows the release priority (in the arcade game Blinky,
+/// Pinky, Inky and Clyde). The limits per ghost come from their [GhostBehaviour]. Blinky and Pinky can always
 /// leave the house at the beginning of the game.
 ///
 /// If pacman dies, the per ghost counter is switched with a newly initialized global one (while retaining
 /// the per ghost one). The waiting ghost can now leave when its predefined limit is reached. When
-/// Clyde left the house, the counter switches back to the per ghost one.
+/// the last ghost (Clyde) left the house, the counter switches back to the per ghost one.
 ///
 /// There is also a timer active. If the timer reaches zero, the waiting ghost can return immediately.
 /// The timer gets reset when pacman eats a dot.
 #[derive(Resource)]
 pub struct GhostHouseGate {
-    released_ghosts: HashSet<Ghost>,
+    released_ghosts: HashSet<GhostSlot>,
     ghost_preference_iterator: GhostPreferenceIterator,
-    current_waiting_ghost: Ghost,
+    current_waiting_ghost: GhostSlot,
     counter: Counter,
     release_timer: Timer,
 }
 
 impl GhostHouseGate {
-    pub fn new(level: &Level) -> Self {
-        let mut iterator = GhostPreferenceIterator::new();
-        let current_waiting_ghost = iterator.next().unwrap();
+    /// Create the gate for the ghosts in the given slots.
+    pub fn new(level: &Level, mut slots: Vec<(GhostSlot, &GhostBehaviour)>) -> Self {
+        slots.sort_by_key(|(slot, _)| *slot);
+        let mut iterator = GhostPreferenceIterator::new(slots.iter().map(|(slot, _)| *slot).collect());
+        let current_waiting_ghost = iterator.next().expect("at least one ghost should be in the house");
 
         GhostHouseGate {
-            released_ghosts: HashSet::with_capacity(NUM_GHOST_TYPES),
+            released_ghosts: HashSet::with_capacity(iterator.len()),
             ghost_preference_iterator: iterator,
             current_waiting_ghost,
-            counter: Counter::new(level),
+            counter: Counter::new(level, &slots),
             release_timer: Self::create_release_timer_for_level(level),
         }
     }
@@ -58,9 +63,9 @@ impl GhostHouseGate {
         }
     }
 
-    /// Ask the gate if the given ghost type can be released.
-    pub fn ghost_can_leave_house(&self, ghost: &Ghost) -> bool {
-        self.released_ghosts.contains(ghost)
+    /// Ask the gate if the ghost in the given slot can be released.
+    pub fn ghost_can_leave_house(&self, slot: &GhostSlot) -> bool {
+        self.released_ghosts.contains(slot)
     }
 
     /// Increment the current counter. Typically when a dot was eaten.
@@ -75,7 +80,7 @@ impl GhostHouseGate {
         self.counter.switch_to_global();
         self.release_timer.reset();
         self.released_ghosts.clear();
-        self.ghost_preference_iterator = GhostPreferenceIterator::new();
+        self.ghost_preference_iterator.restart();
         self.current_waiting_ghost = self.ghost_preference_iterator.next().expect("first item should exists");
     }
 
@@ -93,8 +98,53 @@ impl GhostHouseGate {
         }
     }
 
+    /// Return the counters, the timer and which ghosts are released, to save them. The ghosts are ordered
+    /// by their slot, so the same progress is always saved the same way.
+    pub fn save(&self) -> SavedGhostHouseGate {
+        let mut released_ghosts = self.released_ghosts.iter().map(|slot| **slot).collect::<Vec<_>>();
+        released_ghosts.sort();
+        let mut per_ghost_counters = self.counter.per_ghost_counter.ghost_counter_map
+            .iter()
+            .map(|(slot, count)| (**slot, *count))
+            .collect::<Vec<_>>();
+        per_ghost_counters.sort();
+
+        SavedGhostHouseGate {
+            released_ghosts,
+            preference_position: self.ghost_preference_iterator.current,
+            current_waiting_ghost: *self.current_waiting_ghost,
+            global_counter_active: matches!(self.counter.active_counter, Global),
+            per_ghost_counters,
+            global_counter: self.counter.global_counter.as_ref().map(|counter| counter.value),
+            release_timer_elapsed_secs: self.release_timer.elapsed_secs(),
+        }
+    }
+
+    /// Continue from saved counters. The gate must be created for the same level and ghosts as the saved one.
+    pub fn restore(&mut self, saved: &SavedGhostHouseGate) {
+        self.released_ghosts = saved.released_ghosts.iter().map(|slot| GhostSlot(*slot)).collect();
+        self.ghost_preference_iterator.current = saved.preference_position;
+        self.current_waiting_ghost = GhostSlot(saved.current_waiting_ghost);
+
+        for (slot, count) in &saved.per_ghost_counters {
+            if let Some(counter) = self.counter.per_ghost_counter.ghost_counter_map.get_mut(&GhostSlot(*slot)) {
+                *counter = *count
+            }
+        }
+
+        self.counter.global_counter = saved.global_counter.map(|value| GlobalCounter {
+            value,
+            ghost_limit_map: self.counter.global_limit_map.clone(),
+        });
+        self.counter.active_counter = match (saved.global_counter_active, &self.counter.global_counter) {
+            (true, Some(_)) => Global,
+            _ => PerGhost
+        };
+        self.release_timer.set_elapsed(Duration::from_secs_f32(saved.release_timer_elapsed_secs));
+    }
+
     fn all_ghosts_released(&self) -> bool {
-        self.released_ghosts.len() == NUM_GHOST_TYPES
+        self.released_ghosts.len() == self.ghost_preference_iterator.len()
     }
 
     fn release_current_waiting_ghost(&mut self) {
@@ -106,26 +156,48 @@ impl GhostHouseGate {
     }
 }
 
+/// The progress of a [GhostHouseGate], with the ghosts identified by their slot.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SavedGhostHouseGate {
+    released_ghosts: Vec<usize>,
+    /// How many ghosts were already taken from the preference order
+    preference_position: usize,
+    current_waiting_ghost: usize,
+    global_counter_active: bool,
+    per_ghost_counters: Vec<(usize, usize)>,
+    global_counter: Option<usize>,
+    release_timer_elapsed_secs: f32,
+}
+
 struct GhostPreferenceIterator {
-    ghost_preferences: [Ghost; NUM_GHOST_TYPES],
+    ghost_preferences: Vec<GhostSlot>,
     current: usize,
 }
 
 impl GhostPreferenceIterator {
-    fn new() -> Self {
+    fn new(ghost_preferences: Vec<GhostSlot>) -> Self {
         GhostPreferenceIterator {
-            ghost_preferences: [Blinky, Pinky, Inky, Clyde],
+            ghost_preferences,
             current: 0,
         }
     }
+
+    fn restart(&mut self) {
+        self.current = 0
+    }
+
+    /// The amount of all ghosts, no matter how many were already returned.
+    fn len(&self) -> usize {
+        self.ghost_preferences.len()
+    }
 }
 
 impl Iterator for GhostPreferenceIterator {
-    type Item = Ghost;
+    type Item = GhostSlot;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.current {
-            c if c >= NUM_GHOST_TYPES => None,
+            c if c >= self.ghost_preferences.len() => None,
             ref mut c => {
                 let next = self.ghost_preferences[*c];
                 *c += 1;
@@ -138,19 +210,21 @@ impl Iterator for GhostPreferenceIterator {
 pub(crate) struct Counter {
     active_counter: ActiveCounter,
     per_ghost_counter: PerGhostCounter,
-    global_counter: Option<GlobalCounter>
+    global_counter: Option<GlobalCounter>,
+    global_limit_map: HashMap<GhostSlot, usize>,
 }
 
 impl Counter {
-    pub fn new(level: &Level) -> Self {
+    pub fn new(level: &Level, slots: &[(GhostSlot, &GhostBehaviour)]) -> Self {
         Counter {
             active_counter: PerGhost,
-            per_ghost_counter: PerGhostCounter::new_for_level(level),
-            global_counter: None
+            per_ghost_counter: PerGhostCounter::new_for_level(level, slots),
+            global_counter: None,
+            global_limit_map: create_ghost_value_map(slots, |behaviour| behaviour.global_dot_limit),
         }
     }
 
-    pub fn increment(&mut self, current_ghost: &Ghost) {
+    pub fn increment(&mut self, current_ghost: &GhostSlot) {
         match self.active_counter {
             PerGhost => self.per_ghost_counter.increment(current_ghost),
             Global => self.global_counter.as_mut().unwrap().increment()
@@ -159,13 +233,13 @@ impl Counter {
 
     pub fn switch_to_global(&mut self) {
         self.active_counter = Global;
-        self.global_counter = Some(GlobalCounter::new())
+        self.global_counter = Some(GlobalCounter::new(self.global_limit_map.clone()))
     }
 
     /// Check if the limit for the current ghost is reached.
     ///
     /// Also switches from the global counter to the per ghost counter if the global counter is finished.
-    pub fn limit_reached(&mut self, current_ghost: &Ghost) -> bool {
+    pub fn limit_reached(&mut self, current_ghost: &GhostSlot) -> bool {
         match self.active_counter {
             PerGhost => self.per_ghost_counter.limit_reached_for_ghost(current_ghost),
             Global => {
@@ -186,49 +260,37 @@ enum ActiveCounter {
 }
 
 struct PerGhostCounter {
-    ghost_counter_map: HashMap<Ghost, usize>,
-    ghost_limit_map: HashMap<Ghost, usize>,
+    ghost_counter_map: HashMap<GhostSlot, usize>,
+    ghost_limit_map: HashMap<GhostSlot, usize>,
 }
 
 impl PerGhostCounter {
-    fn new_for_level(level: &Level) -> Self {
-        let ghost_counter_map = create_ghost_value_map(0, 0, 0, 0);
-
-        match **level {
-            1 => PerGhostCounter {
-                ghost_counter_map,
-                ghost_limit_map: create_ghost_value_map(0, 0, 30, 60),
-            },
-            2 => PerGhostCounter {
-                ghost_counter_map,
-                ghost_limit_map: create_ghost_value_map(0, 0, 0, 50),
-            },
-            _ => PerGhostCounter {
-                ghost_counter_map,
-                ghost_limit_map: create_ghost_value_map(0, 0, 0, 0),
-            }
+    fn new_for_level(level: &Level, slots: &[(GhostSlot, &GhostBehaviour)]) -> Self {
+        PerGhostCounter {
+            ghost_counter_map: create_ghost_value_map(slots, |_| 0),
+            ghost_limit_map: create_ghost_value_map(slots, |behaviour| behaviour.dot_limit_for(level)),
         }
     }
 
-    fn increment(&mut self, current_ghost: &Ghost) {
+    fn increment(&mut self, current_ghost: &GhostSlot) {
         *self.ghost_counter_map.get_mut(current_ghost).unwrap() += 1
     }
 
-    fn limit_reached_for_ghost(&self, current_ghost: &Ghost) -> bool {
+    fn limit_reached_for_ghost(&self, current_ghost: &GhostSlot) -> bool {
         self.ghost_counter_map.get(current_ghost).unwrap() == self.ghost_limit_map.get(current_ghost).unwrap()
     }
 }
 
 struct GlobalCounter {
     value: usize,
-    ghost_limit_map: HashMap<Ghost, usize>,
+    ghost_limit_map: HashMap<GhostSlot, usize>,
 }
 
 impl GlobalCounter {
-    fn new() -> Self {
+    fn new(ghost_limit_map: HashMap<GhostSlot, usize>) -> Self {
         GlobalCounter {
             value: 0,
-            ghost_limit_map: create_ghost_value_map(0, 7, 17, 32)
+            ghost_limit_map,
         }
     }
 
@@ -236,20 +298,99 @@ impl GlobalCounter {
         self.value += 1
     }
 
-    fn limit_reached_for_ghost(&self, current_ghost: &Ghost) -> bool {
+    fn limit_reached_for_ghost(&self, current_ghost: &GhostSlot) -> bool {
         *self.ghost_limit_map.get(current_ghost).unwrap() == self.value
     }
 
+    /// The global counter is finished when the limit of the last ghost to leave is reached.
     fn is_finished(&self) -> bool {
-        *self.ghost_limit_map.get(&Clyde).unwrap() == self.value
+        self.ghost_limit_map.values().max().is_some_and(|limit| *limit == self.value)
     }
 }
 
-fn create_ghost_value_map(blinky_val: usize, pinky_val: usize, inky_val: usize, clyde_val: usize) -> HashMap<Ghost, usize> {
-    let mut map = HashMap::with_capacity(4);
-    map.insert(Blinky, blinky_val);
-    map.insert(Pinky, pinky_val);
-    map.insert(Inky, inky_val);
-    map.insert(Clyde, clyde_val);
-    map
-}
\ No newline at end of file
+fn create_ghost_value_map(
+    slots: &[(GhostSlot, &GhostBehaviour)],
+    value: impl Fn(&GhostBehaviour) -> usize,
+) -> HashMap<GhostSlot, usize> {
+    slots
+        .iter()
+        .map(|(slot, behaviour)| (*slot, value(behaviour)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::prelude::*;
+
+    fn create_gate(behaviours: &GhostBehaviours) -> GhostHouseGate {
+        let slots = behaviours
+            .by_release_priority()
+            .iter()
+            .enumerate()
+            .map(|(i, ghost)| (GhostSlot(i), behaviours.get(ghost)))
+            .collect();
+
+        GhostHouseGate::new(&Level(1), slots)
+    }
+
+    fn assert_same_progress(a: &GhostHouseGate, b: &GhostHouseGate) {
+        assert_eq!(a.save(), b.save());
+
+        for slot in (0..4).map(GhostSlot) {
+            assert_eq!(a.ghost_can_leave_house(&slot), b.ghost_can_leave_house(&slot));
+        }
+    }
+
+    #[test]
+    fn a_restored_gate_continues_with_the_per_ghost_counters() {
+        let behaviours = GhostBehaviours::default();
+        let mut gate = create_gate(&behaviours);
+
+        // blinky and pinky leave immediately
+        gate.update(Duration::ZERO);
+        gate.update(Duration::ZERO);
+
+        for _ in 0..10 {
+            gate.increment_counter();
+        }
+
+        gate.update(Duration::from_secs_f32(1.5));
+
+        let mut restored = create_gate(&behaviours);
+        restored.restore(&gate.save());
+        assert_same_progress(&gate, &restored);
+
+        // the release timer continues where it stopped
+        gate.update(Duration::from_secs_f32(2.5));
+        restored.update(Duration::from_secs_f32(2.5));
+        assert!(restored.ghost_can_leave_house(&GhostSlot(2)));
+        assert_same_progress(&gate, &restored);
+    }
+
+    #[test]
+    fn a_restored_gate_continues_with_the_global_counter() {
+        let behaviours = GhostBehaviours::default();
+        let mut gate = create_gate(&behaviours);
+        gate.update(Duration::ZERO);
+        gate.switch_to_global_counter();
+
+        for _ in 0..3 {
+            gate.increment_counter();
+        }
+
+        let mut restored = create_gate(&behaviours);
+        restored.restore(&gate.save());
+        assert_same_progress(&gate, &restored);
+
+        for _ in 0..4 {
+            gate.increment_counter();
+            restored.increment_counter();
+            gate.update(Duration::ZERO);
+            restored.update(Duration::ZERO);
+        }
+
+        assert_same_progress(&gate, &restored);
+    }
+}
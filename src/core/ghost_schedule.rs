@@ -1,5 +1,6 @@
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::core::prelude::Level;
@@ -82,16 +83,21 @@ impl ScheduleByLevel {
         &self,
         level: &Level,
     ) -> GhostSchedule {
-        self.level_schedule_map
-            .get(level)
-            .unwrap_or(&self.default_schedule)
-            .clone()
+        GhostSchedule {
+            level: **level,
+            ..self.level_schedule_map
+                .get(level)
+                .unwrap_or(&self.default_schedule)
+                .clone()
+        }
     }
 }
 
 /// Defines what a ghost will do (chase or retreat) based on the passed time since the level started.
 #[derive(Resource, Reflect, Clone)]
 pub struct GhostSchedule {
+    /// The level the schedule was created for
+    level: usize,
     current_phase_index: usize,
     current_phase_timer: Option<Timer>,
     phases: Vec<Phase>,
@@ -102,6 +108,7 @@ impl GhostSchedule {
         let phases = phases.into_iter().collect::<Vec<_>>();
 
         GhostSchedule {
+            level: 0,
             current_phase_index: 0,
             current_phase_timer: phases
                 .first()
@@ -111,6 +118,10 @@ impl GhostSchedule {
         }
     }
 
+    pub fn is_for_level(&self, level: &Level) -> bool {
+        self.level == **level
+    }
+
     pub fn current_state(&self) -> GhostState {
         self.phases[self.current_phase_index].state
     }
@@ -128,6 +139,24 @@ impl GhostSchedule {
         }
     }
 
+    /// Return how far the schedule has proceeded, to save it.
+    pub fn save(&self) -> SavedGhostSchedule {
+        SavedGhostSchedule {
+            phase: self.current_phase_index,
+            elapsed_secs: self.current_phase_timer.as_ref().map(Timer::elapsed_secs).unwrap_or_default(),
+        }
+    }
+
+    /// Continue the schedule from a saved progress. The schedule must be the one of the saved level.
+    pub fn restore(&mut self, saved: &SavedGhostSchedule) {
+        self.current_phase_index = saved.phase.min(self.phases.len() - 1);
+        self.current_phase_timer = self.phases[self.current_phase_index].phase_timer();
+
+        if let Some(ref mut timer) = self.current_phase_timer {
+            timer.set_elapsed(Duration::from_secs_f32(saved.elapsed_secs));
+        }
+    }
+
     fn switch_to_next_phase(&mut self) {
         if self.current_phase_index < self.phases.len() - 1 {
             self.current_phase_index += 1;
@@ -136,6 +165,13 @@ impl GhostSchedule {
     }
 }
 
+/// The current phase of a [GhostSchedule] and how long it is already active.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq)]
+pub struct SavedGhostSchedule {
+    pub phase: usize,
+    pub elapsed_secs: f32,
+}
+
 /// Tells which state a ghost should enter and for how long
 #[derive(Reflect, Clone)]
 pub struct Phase {
@@ -162,3 +198,39 @@ impl Phase {
         Some(Timer::from_seconds(self.time?, TimerMode::Once))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::prelude::*;
+
+    fn schedule() -> GhostSchedule {
+        ScheduleByLevel::new().get_schedule_for_level(&Level(1))
+    }
+
+    #[test]
+    fn a_restored_schedule_continues_in_the_same_phase() {
+        let mut schedule = schedule();
+        schedule.update(Duration::from_secs(7));
+        schedule.update(Duration::from_secs(3));
+
+        let mut restored = schedule();
+        restored.restore(&schedule.save());
+
+        assert_eq!(restored.save(), SavedGhostSchedule { phase: 1, elapsed_secs: 3.0 });
+        assert_eq!(restored.current_state(), Chase);
+
+        restored.update(Duration::from_secs(17));
+        assert_eq!(restored.current_state(), Scatter);
+    }
+
+    #[test]
+    fn a_restored_schedule_stays_in_its_last_phase() {
+        let mut restored = schedule();
+        restored.restore(&SavedGhostSchedule { phase: 42, elapsed_secs: 1.0 });
+
+        assert_eq!(restored.save(), SavedGhostSchedule { phase: 7, elapsed_secs: 0.0 });
+        assert_eq!(restored.current_state(), Chase);
+    }
+}
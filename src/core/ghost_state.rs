@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub(super) struct GhostStatePlugin;
 
@@ -11,7 +12,7 @@ impl Plugin for GhostStatePlugin {
 }
 
 /// The current state of a ghost
-#[derive(Component, Reflect, Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
 pub enum GhostState {
     /// Move to the ghost corner
     Scatter,
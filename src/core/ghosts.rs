@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub(super) struct GhostPlugin;
 
@@ -6,20 +7,29 @@ impl Plugin for GhostPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Ghost>()
+            .register_type::<GhostSlot>()
             .register_type::<CurrentlyEatenGhost>()
         ;
     }
 }
 
-#[derive(Component, Reflect, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[derive(Component, Reflect, Serialize, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub enum Ghost {
     #[default]
     Blinky,
     Pinky,
     Inky,
     Clyde,
+    /// A ghost that is not part of the arcade game. Its behaviour must be registered in [GhostBehaviours](crate::core::ghost_behaviour::GhostBehaviours).
+    Custom(u8),
 }
 
+/// The spawn slot of a ghost in the ghost house. Identifies a ghost even if the same ghost type
+/// is in the maze multiple times. Ghosts in lower slots leave the house first.
+#[derive(Component, Reflect, Deref, Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[reflect(Component)]
+pub struct GhostSlot(pub usize);
+
 /// Resource that holds the entity id of the ghost that is currently eaten by pacman
 /// The currently eaten ghost be known to ste him invisible while the ghost eaten pause is active
 #[derive(Resource, Reflect, Deref)]
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::core::prelude::Fruit;
+use crate::core::prelude::{Fruit, Ghost};
 
 pub(super) struct InteractionsPlugin;
 
@@ -20,9 +20,9 @@ impl Plugin for InteractionsPlugin {
     }
 }
 
-/// Fired when pacman was hit by a ghost.
-#[derive(Message, Reflect)]
-pub struct PacmanWasHit;
+/// Fired when pacman was hit by a ghost. Contains the ghost that hit pacman.
+#[derive(Message, Reflect, Copy, Clone)]
+pub struct PacmanWasHit(pub Ghost);
 
 /// Fired when Pacman ate a ghost in frightened state.
 /// Contains the eaten ghost entity and the transform to show a score on the ghosts
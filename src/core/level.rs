@@ -8,6 +8,7 @@ impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Level>()
+            .init_resource::<StartingLevel>()
         ;
 
         #[cfg(debug_assertions)]
@@ -25,4 +26,14 @@ impl Level {
     pub fn increase(&mut self) {
         **self += 1
     }
+}
+
+/// The level every game starts in.
+#[derive(Resource, Deref)]
+pub struct StartingLevel(pub usize);
+
+impl Default for StartingLevel {
+    fn default() -> Self {
+        StartingLevel(1)
+    }
 }
\ No newline at end of file
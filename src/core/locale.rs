@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_common_assets::json::JsonAssetPlugin;
+use serde::Deserialize;
+
+use crate::core::prelude::*;
+
+pub(super) struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(JsonAssetPlugin::<Locale>::new(&["locale.json"]))
+            .register_type::<Locale>()
+            .init_resource::<LocalePath>()
+            .add_systems(OnEnter(Setup(CreateSpriteSheets)), insert_active_locale)
+        ;
+    }
+}
+
+/// The asset path of the locale the game starts with.
+#[derive(Resource, Deref)]
+pub struct LocalePath(pub String);
+
+impl Default for LocalePath {
+    fn default() -> Self {
+        LocalePath(DEFAULT_LOCALE_PATH.to_string())
+    }
+}
+
+/// The texts of the UI in one language, by their message id (like "game_over").
+///
+/// Every locale is preloaded like all other assets, so the language can be switched at any time.
+/// The active locale is inserted as resource.
+#[derive(Asset, Resource, Reflect, Deserialize, Clone)]
+pub struct Locale {
+    /// The name of the language, in the language itself
+    pub name: String,
+    /// Fonts for scripts the default font cannot display, tried in the given order
+    #[serde(default)]
+    pub fonts: Vec<String>,
+    pub strings: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Return the text with the given message id. If the locale has no such text, the id itself is returned,
+    /// so missing translations are easy to spot.
+    pub fn text<'a>(&'a self, id: &'a str) -> &'a str {
+        self.strings.get(id).map(String::as_str).unwrap_or(id)
+    }
+}
+
+/// A UI text which is shown in the active language and updated when the language changes.
+#[derive(Component, Deref, Clone)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(id: impl ToString) -> Self {
+        LocalizedText(id.to_string())
+    }
+}
+
+/// The characters every loaded font can display. Parsing a font is expensive, so this is only done
+/// once when the locale is loaded and not for every text.
+#[derive(Resource, Default, Deref)]
+pub struct FontCoverage(HashMap<AssetId<Font>, HashSet<char>>);
+
+/// Selects the font a text is rendered with, so texts in scripts the default font does not
+/// support can still be displayed.
+#[derive(SystemParam)]
+pub struct LocaleFonts<'w> {
+    locale: Res<'w, Locale>,
+    asset_server: Res<'w, AssetServer>,
+    coverage: Res<'w, FontCoverage>,
+}
+
+impl LocaleFonts<'_> {
+    /// Return the first font which can display every character of the given text. The default font is tried
+    /// first, then the fonts of the active locale and then the fallback font. If none of them fits, the default font is used.
+    pub fn font_for(&self, text: &str) -> Handle<Font> {
+        let candidates = std::iter::once(FONT)
+            .chain(self.locale.fonts.iter().map(String::as_str))
+            .chain(std::iter::once(FALLBACK_FONT));
+
+        for path in candidates {
+            let handle = self.asset_server.load::<Font>(path);
+
+            if self.coverage.get(&handle.id()).is_some_and(|chars| can_display(chars, text)) {
+                return handle;
+            }
+        }
+
+        self.asset_server.load(FONT)
+    }
+}
+
+/// Return every character the given font file has a glyph for. A font which cannot be parsed covers nothing.
+fn characters_of(font_data: &[u8]) -> HashSet<char> {
+    let Ok(face) = ttf_parser::Face::parse(font_data, 0) else {
+        return HashSet::new();
+    };
+
+    let mut chars = HashSet::new();
+
+    for subtable in face.tables().cmap.into_iter().flat_map(|cmap| cmap.subtables) {
+        if subtable.is_unicode() {
+            subtable.codepoints(|c| chars.extend(char::from_u32(c)));
+        }
+    }
+
+    chars
+}
+
+fn can_display(chars: &HashSet<char>, text: &str) -> bool {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| chars.contains(&c))
+}
+
+/// Insert the active locale. As every font is preloaded, the characters of all fonts are collected here
+/// as well, so later switches of the language do not need to parse the fonts again.
+fn insert_active_locale(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    locale_path: Res<LocalePath>,
+    locales: Res<Assets<Locale>>,
+    fonts: Res<Assets<Font>>,
+) {
+    let handle = asset_server.load::<Locale>(locale_path.as_str());
+    let locale = locales
+        .get(&handle)
+        .unwrap_or_else(|| panic!("locale {} was not loaded!", **locale_path));
+    commands.insert_resource(locale.clone());
+    commands.insert_resource(FontCoverage(
+        fonts
+            .iter()
+            .map(|(id, font)| (id, characters_of(&font.data)))
+            .collect()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    use crate::core::locale::{can_display, characters_of, Locale};
+    use crate::core::prelude::*;
+
+    const LOCALES: [&str; 3] = ["en", "de", "ru"];
+
+    fn load_locale(name: &str) -> Locale {
+        let path = Path::new("assets/locales").join(format!("{name}.locale.json"));
+        let json = std::fs::read_to_string(path).expect("the locale should exist");
+        serde_json::from_str(&json).expect("the locale should be valid")
+    }
+
+    fn characters_of_font(path: &str) -> HashSet<char> {
+        characters_of(&std::fs::read(Path::new("assets").join(path)).expect("the font should exist"))
+    }
+
+    #[test]
+    fn every_locale_has_the_same_texts() {
+        let english = load_locale("en");
+        let mut expected = english.strings.keys().collect::<Vec<_>>();
+        expected.sort();
+
+        for name in LOCALES {
+            let locale = load_locale(name);
+            let mut ids = locale.strings.keys().collect::<Vec<_>>();
+            ids.sort();
+
+            assert_eq!(ids, expected, "locale {name} has different texts than english");
+        }
+    }
+
+    #[test]
+    fn every_text_can_be_displayed_by_a_font_of_its_locale() {
+        for name in LOCALES {
+            let locale = load_locale(name);
+            let fonts = std::iter::once(FONT)
+                .chain(locale.fonts.iter().map(String::as_str))
+                .chain(std::iter::once(FALLBACK_FONT))
+                .map(characters_of_font)
+                .collect::<Vec<_>>();
+
+            for text in locale.strings.values().chain(std::iter::once(&locale.name)) {
+                assert!(
+                    fonts.iter().any(|chars| can_display(chars, text)),
+                    "no font of locale {name} can display {text}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn english_texts_are_displayed_with_the_default_font() {
+        let chars = characters_of_font(FONT);
+
+        for text in load_locale("en").strings.values() {
+            assert!(can_display(&chars, text), "the default font cannot display {text}");
+        }
+    }
+
+    #[test]
+    fn whitespace_can_always_be_displayed() {
+        let chars = HashSet::from(['A', 'B']);
+
+        assert!(can_display(&chars, "A B\tA"));
+        assert!(can_display(&chars, ""));
+        assert!(!can_display(&chars, "A C"));
+    }
+
+    #[test]
+    fn a_font_which_cannot_be_parsed_displays_nothing() {
+        let chars = characters_of(b"not a font");
+
+        assert!(chars.is_empty());
+        assert!(!can_display(&chars, "A"));
+    }
+
+    #[test]
+    fn a_text_is_found_by_its_id() {
+        let locale = load_locale("de");
+
+        assert_eq!(locale.text("ready"), "Bereit!");
+    }
+
+    #[test]
+    fn a_missing_text_is_shown_as_its_id() {
+        let locale = load_locale("en");
+
+        assert_eq!(locale.text("not_translated"), "not_translated");
+    }
+}
@@ -1,8 +1,9 @@
 use crate::core::prelude::*;
 use bevy::prelude::*;
 use std::f32::consts::PI;
+use std::ops::{Range, RangeInclusive};
 
-pub(super) struct MapPlugin;
+pub(crate) struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(
@@ -27,8 +28,20 @@ impl Plugin for MapPlugin {
             .register_type::<PacmanSpawn>()
             .register_type::<GhostHouse>()
             .register_type::<GhostSpawn>()
+            .register_type::<GhostHouseLayout>()
             .register_type::<GhostCorner>()
-            .register_type::<OneWay>();
+            .register_type::<OneWay>()
+            .init_resource::<MapScenePath>();
+    }
+}
+
+/// The asset path of the map scene the maze is built from.
+#[derive(Resource, Deref)]
+pub struct MapScenePath(pub String);
+
+impl Default for MapScenePath {
+    fn default() -> Self {
+        MapScenePath(MAP_SCENE_PATH.to_string())
     }
 }
 
@@ -40,6 +53,37 @@ pub struct Map {
     pub height: usize,
 }
 
+impl Map {
+    /// The columns of the maze from left to right, starting at 0.
+    pub fn columns(&self) -> Range<isize> {
+        0..self.width as isize
+    }
+
+    /// The rows of the maze from bottom to top. The map creator writes the top row first with the
+    /// height as y and stops after row 1, so the rows start at 1.
+    pub fn rows(&self) -> RangeInclusive<isize> {
+        1..=self.height as isize
+    }
+
+    /// Return if the given position is a tile of the maze.
+    pub fn contains(
+        &self,
+        pos: &Pos,
+    ) -> bool {
+        self.columns().contains(&pos.x()) && self.rows().contains(&pos.y())
+    }
+
+    /// Return the area the maze covers in world coordinates. Tiles are centered on their position.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(
+            (self.columns().start as f32 - 0.5) * FIELD_SIZE,
+            (*self.rows().start() as f32 - 0.5) * FIELD_SIZE,
+            (self.columns().end as f32 - 0.5) * FIELD_SIZE,
+            (*self.rows().end() as f32 + 0.5) * FIELD_SIZE,
+        )
+    }
+}
+
 /// An entity with this component spans either one or more tiles on the map.
 #[derive(Component, Reflect, Copy, Clone)]
 #[reflect(Component)]
@@ -182,13 +226,30 @@ pub struct TunnelHallway;
 #[derive(Component, Reflect)]
 pub struct GhostHouse;
 
+/// The points every ghost passes when leaving or entering the ghost house. Lives on the [GhostHouse] entity.
+#[derive(Component, Reflect, Copy, Clone)]
+pub struct GhostHouseLayout {
+    /// The two tiles directly in front of the entrance
+    pub entrance_positions: [Pos; 2],
+    pub entrance_coordinates: Vec3,
+    /// The direction a ghost takes when it left the house and reached the entrance
+    pub entrance_direction: Dir,
+    pub center_coordinates: Vec3,
+    /// The direction from the house center to the entrance
+    pub exit_direction: Dir,
+}
+
 /// Spawn area of a ghost
 #[derive(Component, Reflect, Copy, Clone)]
 pub struct GhostSpawn {
     pub ghost: Ghost,
+    pub slot: GhostSlot,
     pub coordinates: Vec3,
     pub spawn_direction: Dir,
     pub positions: [Pos; 2],
+    /// Where the ghost gets revived after it was eaten. Differs from the spawn coordinates
+    /// if the ghost spawns outside the house or outside its center row.
+    pub respawn_coordinates: Vec3,
 }
 
 /// Marks a tile as one way. A one way is used to mark an intersection as a point where
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use pad::position::Position;
 
 use crate::core::edibles::EdiblesPlugin;
+use crate::core::ghost_behaviour::GhostBehaviourPlugin;
 use crate::core::ghost_schedule::GhostSchedulePlugin;
 use crate::core::ghost_state::GhostStatePlugin;
 use crate::core::ghosts::GhostPlugin;
@@ -15,19 +16,26 @@ use crate::core::specs_per_level::SpecsPerLevelPlugin;
 use crate::core::speed::SpeedPlugin;
 use crate::core::target::TargetPlugin;
 use crate::core::animation::AnimationPlugin;
+use crate::core::audio_settings::AudioSettingsPlugin;
+use crate::core::camera::CameraPlugin;
+use crate::core::locale::LocalePlugin;
 use crate::core::direction::Dir;
 use crate::core::game_state::GameStatePlugin;
 use crate::core::music::MusicPlugin;
 use crate::core::position::Pos;
 use crate::core::restart_game::RestartGamePlugin;
+use crate::core::ruleset::RulesetPlugin;
+use crate::core::save_game::SaveGamePlugin;
 use crate::core::sound_effect::SoundEffectPlugin;
 use crate::core::system_sets::SystemSetsPlugin;
+use crate::core::theme::ThemePlugin;
 
 pub mod position;
 pub mod direction;
 pub mod edibles;
 pub mod pacman;
 pub mod ghosts;
+pub mod ghost_behaviour;
 pub mod map;
 pub mod target;
 pub mod helper;
@@ -46,7 +54,14 @@ pub mod constants;
 pub mod game_state;
 pub mod system_sets;
 pub mod sound_effect;
+pub mod audio_settings;
 pub mod music;
+pub mod tile_graph;
+pub mod ruleset;
+pub mod theme;
+pub mod camera;
+pub mod locale;
+pub mod save_game;
 pub mod prelude;
 mod restart_game;
 
@@ -77,9 +92,16 @@ impl Plugin for CorePlugin {
             ))
             .add_plugins((
                 SoundEffectPlugin,
+                AudioSettingsPlugin,
                 MusicPlugin,
                 RestartGamePlugin,
-                SystemSetsPlugin
+                RulesetPlugin,
+                GhostBehaviourPlugin,
+                SystemSetsPlugin,
+                ThemePlugin,
+                CameraPlugin,
+                LocalePlugin,
+                SaveGamePlugin
             ))
         ;
     }
@@ -18,9 +18,9 @@ impl Plugin for MusicPlugin {
 #[derive(Component, Reflect)]
 pub struct BackgroundTrack;
 
-/// Marker for the siren background track
-#[derive(Component, Reflect)]
-pub struct SirenBackground;
+/// Marker for the siren background track of the given stage (see [SirenRules])
+#[derive(Component, Reflect, Deref)]
+pub struct SirenBackground(pub usize);
 
 /// Marker for the frightened background track
 #[derive(Component, Reflect)]
@@ -40,23 +40,17 @@ pub struct BackgroundMusic {
 impl BackgroundMusic {
     pub fn new_muted() -> Self {
         BackgroundMusic {
-            current_track: Siren1,
+            current_track: Siren(0),
             muted: true,
         }
     }
 }
 
 /// Identifiers for the current track that should be played
-#[derive(Reflect)]
+#[derive(Reflect, Copy, Clone, Eq, PartialEq, Debug)]
 pub enum CurrentTrack {
-    /// The siren sound, when the remaining dots are between 100% and 75%
-    Siren1,
-    /// The siren sound, when the remaining dots are between 75% and 50%
-    Siren2,
-    /// The siren sound, when the remaining dots are between 50% and 25%
-    Siren3,
-    /// The siren sound, when the remaining dots are between 25% and 0%
-    Siren4,
+    /// The siren of the given stage. Which stage plays depends on the eaten dots and elroy (see [SirenRules]).
+    Siren(usize),
     /// The music that plays when pacman ate an energizer and the ghosts turn blue
     FrightenedTrack,
     /// The sound that plays when an eaten ghost returns to the ghost house
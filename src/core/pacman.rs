@@ -7,6 +7,13 @@ impl Plugin for PacmanPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Pacman>()
+            .register_type::<PacmanController>()
+            .register_type::<WishedDirection>()
+            .register_type::<InputBuffer>()
+            .register_type::<SelectedController>()
+            .register_type::<RecordedInputs>()
+            .register_type::<ReplayInputs>()
+            .init_resource::<SelectedController>()
         ;
     }
 }
@@ -19,6 +26,81 @@ impl Plugin for PacmanPlugin {
     Dir::Up,
     Speed,
     Sprite,
-    Animations
+    Animations,
+    PacmanController,
+    WishedDirection,
+    InputBuffer
 )]
-pub struct Pacman;
\ No newline at end of file
+pub struct Pacman;
+
+/// Decides where the direction of a pacman comes from.
+///
+/// Every controller only writes the [WishedDirection] of its pacman. Checking for walls and
+/// buffering a direction until pacman is centered enough to take it is the same for every controller.
+#[derive(Component, Reflect, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[reflect(Component)]
+pub enum PacmanController {
+    /// Arrow keys or WASD
+    #[default]
+    Keyboard,
+    /// The d-pad or left stick of any connected gamepad
+    Gamepad,
+    /// The inputs from the [ReplayInputs] resource, one per frame
+    Replay,
+    /// The autopilot, which decides on its own based on the maze, the edibles and the ghosts
+    Bot,
+    /// The wished direction is written from outside the game, for example by a network peer.
+    /// The game itself never touches it.
+    Remote,
+}
+
+/// The direction the controller of a pacman wants to move to right now. None if the controller
+/// has no new wish, in which case the last buffered direction is used.
+#[derive(Component, Reflect, Deref, DerefMut, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[reflect(Component)]
+pub struct WishedDirection(pub Option<Dir>);
+
+/// Saves the wished direction pacman should move to next.
+#[derive(Component, Reflect, Deref, DerefMut, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[reflect(Component)]
+pub struct InputBuffer(pub Option<Dir>);
+
+/// The controller a newly spawned pacman gets.
+#[derive(Resource, Reflect, Deref, DerefMut, Copy, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct SelectedController(pub PacmanController);
+
+/// The wished directions of pacman for every frame the game was running and not paused, so the game can be replayed.
+///
+/// As the inputs are stored per frame, a replay is only exact if the frame times are the same.
+/// Only exists while a game gets recorded, so no inputs are collected otherwise.
+#[derive(Resource, Reflect, Deref, DerefMut, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct RecordedInputs(pub Vec<Option<Dir>>);
+
+/// Inputs which get played back one per frame by pacmans with the [PacmanController::Replay] controller.
+#[derive(Resource, Reflect, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct ReplayInputs {
+    inputs: Vec<Option<Dir>>,
+    next: usize,
+}
+
+impl ReplayInputs {
+    pub fn new(inputs: Vec<Option<Dir>>) -> Self {
+        ReplayInputs { inputs, next: 0 }
+    }
+
+    /// Return the input for the current frame and advance to the next one.
+    /// Returns None when the replay is over.
+    pub fn next_input(&mut self) -> Option<Dir> {
+        let input = self.inputs.get(self.next).copied().flatten();
+        self.next += 1;
+        input
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.inputs.len()
+    }
+}
+
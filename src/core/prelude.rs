@@ -18,9 +18,11 @@ pub use crate::core::helper::*;
 pub use crate::core::edibles::*;
 pub use crate::core::edibles::Fruit::*;
 pub use crate::core::sound_effect::*;
+pub use crate::core::audio_settings::*;
 pub use crate::core::pacman::*;
 pub use crate::core::ghosts::*;
 pub use crate::core::ghosts::Ghost::*;
+pub use crate::core::ghost_behaviour::*;
 pub use crate::core::map::*;
 pub use crate::core::map::Rotation::*;
 pub use crate::core::map::WallType::*;
@@ -33,4 +35,10 @@ pub use crate::core::ghost_schedule::*;
 pub use crate::core::score::*;
 pub use crate::core::music::*;
 pub use crate::core::music::CurrentTrack::*;
-pub use crate::core::restart_game::*;
\ No newline at end of file
+pub use crate::core::restart_game::*;
+pub use crate::core::tile_graph::*;
+pub use crate::core::ruleset::*;
+pub use crate::core::theme::*;
+pub use crate::core::camera::*;
+pub use crate::core::locale::*;
+pub use crate::core::save_game::*;
\ No newline at end of file
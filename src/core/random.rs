@@ -1,24 +1,39 @@
+use std::sync::Mutex;
+
 use bevy::prelude::*;
 use rand::prelude::*;
-use rand::rng;
+use rand::rngs::StdRng;
 
 pub(crate) struct RandomPlugin;
 
 impl Plugin for RandomPlugin {
     fn build(&self, app: &mut App) {
         app
-            .insert_resource(Random)
+            .insert_resource(Random::from_os_rng())
         ;
     }
 }
 
 /// Provides randomly chosen numbers to whoever needs them.
+///
+/// The generator can be seeded, so a game can be played again with the exact same random decisions.
 #[derive(Resource)]
-pub struct Random;
+pub struct Random(Mutex<StdRng>);
 
 impl Random {
+    pub fn from_seed(seed: u64) -> Self {
+        Random(Mutex::new(StdRng::seed_from_u64(seed)))
+    }
+
+    pub fn from_os_rng() -> Self {
+        Random(Mutex::new(StdRng::from_os_rng()))
+    }
+
     pub fn zero_to(&self, n: usize) -> usize {
-        let mut rng = rng();
-        rng.random_range(0..n)
+        self.0.lock().unwrap().random_range(0..n)
+    }
+
+    pub fn between(&self, min: f32, max: f32) -> f32 {
+        self.0.lock().unwrap().random_range(min..=max)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_common_assets::json::JsonAssetPlugin;
+#[cfg(debug_assertions)]
+use bevy_inspector_egui::quick::ResourceInspectorPlugin;
+use serde::Deserialize;
+
+use crate::core::prelude::*;
+
+pub(super) struct RulesetPlugin;
+
+impl Plugin for RulesetPlugin {
+    fn build(
+        &self,
+        app: &mut App,
+    ) {
+        app.add_plugins(JsonAssetPlugin::<Ruleset>::new(&["ruleset.json"]))
+            .register_type::<Ruleset>()
+            .register_type::<FruitRules>()
+            .register_type::<SirenRules>()
+            .register_type::<SirenStage>()
+            .register_type::<EatenGhostNavigation>()
+            .init_resource::<RulesetPath>()
+            .add_systems(OnEnter(Setup(CreateSpriteSheets)), insert_active_ruleset)
+            .add_systems(Update, replace_ruleset_when_changed.run_if(resource_exists::<Ruleset>));
+
+        #[cfg(debug_assertions)]
+        app.add_plugins(ResourceInspectorPlugin::<Ruleset>::default());
+    }
+}
+
+/// The asset path of the ruleset the game is played with.
+#[derive(Resource, Deref)]
+pub struct RulesetPath(pub String);
+
+impl Default for RulesetPath {
+    fn default() -> Self {
+        RulesetPath(DEFAULT_RULESET_PATH.to_string())
+    }
+}
+
+/// Rules of the game which are loaded from a ruleset file, so different
+/// variants of the game can be played without changing the code.
+///
+/// The ruleset is preloaded like every other asset and inserted as resource before the maze spawns.
+/// A ruleset with problems is rejected.
+#[derive(Asset, Resource, Reflect, Deserialize, Clone)]
+pub struct Ruleset {
+    /// The ghosts in the maze, at least one and at most [MAX_GHOSTS]. The same ghost can appear multiple times.
+    /// Every ghost needs a registered [GhostBehaviour].
+    pub ghosts: Vec<Ghost>,
+    pub eaten_ghost_navigation: EatenGhostNavigation,
+    pub fruit: FruitRules,
+    pub siren: SirenRules,
+}
+
+impl Ruleset {
+    /// Return every problem which prevents the game from being played with this ruleset.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        match self.ghosts.len() {
+            0 => problems.push("there are no ghosts".to_string()),
+            n if n > MAX_GHOSTS => problems.push(format!("there are {n} ghosts, but the ghost house only has room for {MAX_GHOSTS}")),
+            _ => {}
+        }
+
+        problems.extend(self.fruit.problems());
+        problems
+    }
+}
+
+/// How eaten ghosts find their way back to the ghost house.
+#[derive(Reflect, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum EatenGhostNavigation {
+    /// Follow the shortest path, which works on every map
+    #[default]
+    ShortestPath,
+    /// At every intersection, take the tile nearest to the entrance (by air), like in the arcade game.
+    /// Might take absurd routes or even loop on custom maps.
+    ArcadeGreedy,
+}
+
+/// Defines when fruits appear, how long they stay and how many points they are worth.
+#[derive(Reflect, Deserialize, Clone)]
+pub struct FruitRules {
+    /// How fruits appear in the maze
+    pub mode: FruitMode,
+    /// A fruit spawns when the amount of eaten dots reaches one of these values
+    pub trigger_dot_counts: Vec<usize>,
+    /// The minimum and maximum seconds a static fruit stays in the maze. The actual time is chosen randomly.
+    pub lifetime_secs: (f32, f32),
+    /// The points pacman gets for eating a fruit
+    pub points: HashMap<Fruit, usize>,
+    /// The fruit for each level, starting at level 1. Every level after the last entry uses the last fruit.
+    pub fruit_per_level: Vec<Fruit>,
+    /// If set, fruits are chosen randomly from random_fruits after this level
+    pub random_fruits_after_level: Option<usize>,
+    /// The fruits to choose from when fruits are chosen randomly
+    pub random_fruits: Vec<Fruit>,
+}
+
+impl FruitRules {
+    /// Return if a fruit should spawn for the given amount of eaten dots.
+    pub fn spawns_fruit_at(
+        &self,
+        num_eaten_dots: usize,
+    ) -> bool {
+        self.trigger_dot_counts.contains(&num_eaten_dots)
+    }
+
+    /// Return the fruit shown for the given level, like in the fruit history beneath the maze.
+    pub fn fruit_for_level(
+        &self,
+        level: &Level,
+    ) -> Fruit {
+        let index = level.saturating_sub(1).min(self.fruit_per_level.len() - 1);
+        self.fruit_per_level[index]
+    }
+
+    /// Return the fruit that should actually spawn in the given level, which might be a random one.
+    pub fn fruit_to_spawn(
+        &self,
+        level: &Level,
+        random: &Random,
+    ) -> Fruit {
+        match self.random_fruits_after_level {
+            Some(l) if **level > l && !self.random_fruits.is_empty() => {
+                self.random_fruits[random.zero_to(self.random_fruits.len())]
+            }
+            _ => self.fruit_for_level(level),
+        }
+    }
+
+    pub fn points_for(
+        &self,
+        fruit: &Fruit,
+    ) -> usize {
+        self.points.get(fruit).copied().unwrap_or_default()
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        if self.fruit_per_level.is_empty() {
+            problems.push("there is no fruit per level".to_string());
+        }
+
+        let (min, max) = self.lifetime_secs;
+
+        if !(0.0 <= min && min <= max) {
+            problems.push(format!("the fruit lifetime ({min}, {max}) is no valid range of seconds"));
+        }
+
+        problems
+    }
+
+    /// Return a random lifetime for a static fruit.
+    pub fn lifetime(
+        &self,
+        random: &Random,
+    ) -> f32 {
+        random.between(self.lifetime_secs.0, self.lifetime_secs.1)
+    }
+}
+
+/// Defines which siren plays in the background while the ghosts chase pacman.
+#[derive(Reflect, Deserialize, Clone)]
+pub struct SirenRules {
+    /// The stages of the siren, ordered by the share of eaten dots they start at. The first stage should start at 0.0.
+    pub stages: Vec<SirenStage>,
+    /// If ghosts with the elroy speed profile push the siren to the last stages, like in the arcade game.
+    /// Elroy 1 plays at least the second to last stage, elroy 2 the last one.
+    pub elroy_stages: bool,
+    /// How long it takes to fade from one background track to another
+    pub crossfade_secs: f32,
+}
+
+/// One stage of the siren.
+#[derive(Reflect, Deserialize, Clone)]
+pub struct SirenStage {
+    /// The share of eaten dots (from 0.0 to 1.0) this stage starts at
+    pub from_eaten_dots: f32,
+    /// The asset path of the track of this stage. Multiple stages can use the same track.
+    pub track: String,
+    /// The playback speed of the track, so stages sharing a track still sound different
+    pub speed: f32,
+}
+
+impl SirenRules {
+    /// Return the index of the siren stage to play for the given share of eaten dots and elroy phase
+    /// (0 if no ghost is elroy yet, else 1 or 2).
+    pub fn stage_for(
+        &self,
+        eaten_dots_share: f32,
+        elroy_phase: usize,
+    ) -> usize {
+        let dot_stage = self
+            .stages
+            .iter()
+            .rposition(|stage| stage.from_eaten_dots <= eaten_dots_share)
+            .unwrap_or_default();
+
+        if !self.elroy_stages || elroy_phase == 0 {
+            return dot_stage;
+        }
+
+        let elroy_stage = (self.stages.len() + elroy_phase.min(2)).saturating_sub(3);
+        dot_stage.max(elroy_stage)
+    }
+}
+
+/// Insert the ruleset the game is played with. If it was not loaded or has problems, the game cannot be played and exits.
+fn insert_active_ruleset(
+    mut commands: Commands,
+    mut exit_writer: MessageWriter<AppExit>,
+    asset_server: Res<AssetServer>,
+    ruleset_path: Res<RulesetPath>,
+    rulesets: Res<Assets<Ruleset>>,
+) {
+    let handle = asset_server.load::<Ruleset>(ruleset_path.as_str());
+
+    let Some(ruleset) = rulesets.get(&handle) else {
+        error!("the ruleset {} was not loaded", **ruleset_path);
+        exit_writer.write(AppExit::error());
+        return;
+    };
+
+    let problems = ruleset.problems();
+
+    if !problems.is_empty() {
+        error!("the ruleset {} cannot be played with: {}", **ruleset_path, problems.join(", "));
+        exit_writer.write(AppExit::error());
+        return;
+    }
+
+    commands.insert_resource(ruleset.clone());
+}
+
+/// Replace the active ruleset when its file was changed, so the game can be tuned while playing.
+///
+/// Changes are only noticed when the assets are watched, which is the case when the game runs with the dev feature.
+fn replace_ruleset_when_changed(
+    mut commands: Commands,
+    mut ruleset_events: MessageReader<AssetEvent<Ruleset>>,
+    asset_server: Res<AssetServer>,
+    ruleset_path: Res<RulesetPath>,
+    rulesets: Res<Assets<Ruleset>>,
+) {
+    if ruleset_events.is_empty() {
+        return;
+    }
+
+    let handle = asset_server.load::<Ruleset>(ruleset_path.as_str());
+
+    if !ruleset_events.read().any(|event| event.is_modified(&handle)) {
+        return;
+    }
+
+    let Some(ruleset) = rulesets.get(&handle) else {
+        return;
+    };
+
+    let problems = ruleset.problems();
+
+    match problems.is_empty() {
+        true => {
+            info!("{} was changed, replacing the ruleset", **ruleset_path);
+            commands.insert_resource(ruleset.clone());
+        }
+        false => error!("{} was changed, but cannot be played with: {}", **ruleset_path, problems.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::assets;
+    use crate::core::prelude::*;
+
+    fn fruit_rules() -> FruitRules {
+        FruitRules {
+            mode: FruitMode::Static,
+            trigger_dot_counts: vec![70, 170],
+            lifetime_secs: (9.0, 10.0),
+            points: HashMap::from([(Cherry, 100)]),
+            fruit_per_level: vec![Cherry, Strawberry],
+            random_fruits_after_level: None,
+            random_fruits: vec![],
+        }
+    }
+
+    #[test]
+    fn the_last_fruit_is_used_for_all_following_levels() {
+        let rules = fruit_rules();
+
+        assert_eq!(rules.fruit_for_level(&Level(1)), Cherry);
+        assert_eq!(rules.fruit_for_level(&Level(2)), Strawberry);
+        assert_eq!(rules.fruit_for_level(&Level(42)), Strawberry);
+    }
+
+    #[test]
+    fn fruits_spawn_only_at_the_trigger_dot_counts() {
+        let rules = fruit_rules();
+
+        assert!(rules.spawns_fruit_at(70));
+        assert!(!rules.spawns_fruit_at(71));
+    }
+
+    fn siren_rules(elroy_stages: bool) -> SirenRules {
+        SirenRules {
+            stages: [0.0, 0.25, 0.5, 0.75]
+                .into_iter()
+                .map(|from_eaten_dots| SirenStage {
+                    from_eaten_dots,
+                    track: assets::sounds::SIREN_OGG.to_string(),
+                    speed: 1.0,
+                })
+                .collect(),
+            elroy_stages,
+            crossfade_secs: 0.5,
+        }
+    }
+
+    #[test]
+    fn the_siren_stage_follows_the_eaten_dots_and_elroy() {
+        let rules = siren_rules(true);
+
+        assert_eq!(rules.stage_for(0.0, 0), 0);
+        assert_eq!(rules.stage_for(0.3, 0), 1);
+        assert_eq!(rules.stage_for(0.3, 1), 2);
+        assert_eq!(rules.stage_for(0.3, 2), 3);
+        assert_eq!(rules.stage_for(0.9, 1), 3);
+        assert_eq!(siren_rules(false).stage_for(0.3, 2), 1);
+    }
+
+    #[test]
+    fn fruit_rules_need_fruits_and_a_valid_lifetime() {
+        assert!(fruit_rules().problems().is_empty());
+
+        let rules = FruitRules {
+            fruit_per_level: vec![],
+            lifetime_secs: (10.0, 9.0),
+            ..fruit_rules()
+        };
+
+        assert_eq!(rules.problems(), vec![
+            "there is no fruit per level",
+            "the fruit lifetime (10, 9) is no valid range of seconds",
+        ]);
+    }
+
+    #[test]
+    fn every_ruleset_in_the_assets_can_be_played_with() {
+        for entry in std::fs::read_dir("assets/rulesets").expect("the rulesets folder should exist") {
+            let path = entry.expect("the ruleset should be readable").path();
+            let json = std::fs::read_to_string(&path).expect("the ruleset should be readable");
+            let ruleset = serde_json::from_str::<Ruleset>(&json).expect("the ruleset should be valid JSON");
+
+            assert_eq!(ruleset.problems(), Vec::<String>::new(), "{} has problems", path.display());
+        }
+    }
+
+    fn ruleset(ghosts: Vec<Ghost>) -> Ruleset {
+        Ruleset {
+            ghosts,
+            eaten_ghost_navigation: EatenGhostNavigation::ShortestPath,
+            fruit: fruit_rules(),
+            siren: siren_rules(true),
+        }
+    }
+
+    #[test]
+    fn the_ghosts_must_fit_into_the_ghost_house() {
+        assert!(ruleset(vec![Blinky]).problems().is_empty());
+        assert!(ruleset(vec![Inky; MAX_GHOSTS]).problems().is_empty());
+        assert_eq!(ruleset(vec![]).problems(), vec!["there are no ghosts"]);
+        assert_eq!(
+            ruleset(vec![Inky; MAX_GHOSTS + 1]).problems(),
+            vec!["there are 11 ghosts, but the ghost house only has room for 10"]
+        );
+    }
+
+    #[test]
+    fn fruits_without_points_are_worth_nothing() {
+        let rules = fruit_rules();
+
+        assert_eq!(rules.points_for(&Cherry), 100);
+        assert_eq!(rules.points_for(&Key), 0);
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::prelude::*;
+
+pub(super) struct SaveGamePlugin;
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_message::<ResumeSavedGame>()
+        ;
+    }
+}
+
+/// Sent when the player wants to continue the saved game.
+#[derive(Message, Copy, Clone)]
+pub struct ResumeSavedGame;
+
+/// A game in progress, saved when the game is quit. When resumed, the game continues from [Game::Ready].
+///
+/// The fruit, an active energizer and ghosts which are in or on their way back to the ghost house
+/// are not saved. Such ghosts start in the ghost house again.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveGame {
+    pub score: usize,
+    pub high_score: usize,
+    pub high_score_was_beaten: bool,
+    pub lives: usize,
+    pub level: usize,
+    pub points_required_for_extra_life: usize,
+    /// The tiles of every dot which was not eaten yet
+    pub dots: Vec<(isize, isize)>,
+    /// The tiles of every energizer which was not eaten yet
+    pub energizers: Vec<(isize, isize)>,
+    pub ghost_schedule: SavedGhostSchedule,
+    pub ghost_house_gate: SavedGhostHouseGate,
+    /// None if pacman was already gone, like after he died
+    pub pacman: Option<SavedPacman>,
+    pub ghosts: Vec<SavedGhost>,
+}
+
+impl SaveGame {
+    /// Load the saved game from the given file. Returns None if the file does not exist or is invalid.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("the saved game should be serializable");
+        std::fs::write(path, json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct SavedPacman {
+    pub x: f32,
+    pub y: f32,
+    pub direction: Dir,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct SavedGhost {
+    /// The [GhostSlot] of the ghost
+    pub slot: usize,
+    pub x: f32,
+    pub y: f32,
+    pub direction: Dir,
+    pub state: GhostState,
+}
+
+/// The file the game is saved in. Without this resource, games are neither saved nor resumed,
+/// so headless games never touch the saved game of the player.
+#[derive(Resource, Deref)]
+pub struct SaveGamePath(pub String);
+
+impl Default for SaveGamePath {
+    fn default() -> Self {
+        SaveGamePath(DEFAULT_SAVE_GAME_PATH.to_string())
+    }
+}
@@ -1,36 +1,74 @@
-use std::time::Duration;
 use bevy::prelude::*;
+use crate::assets;
 
 pub(super) struct SoundEffectPlugin;
 
 impl Plugin for SoundEffectPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_message::<PlaySoundEffect>()
             .register_type::<SoundEffect>()
+            .register_type::<SoundKind>()
         ;
     }
 }
 
+/// Message to play a sound effect. Sound effects are never spawned directly, so the limits
+/// and priorities of every [SoundKind] are respected.
+#[derive(Message, Deref, Copy, Clone, Debug)]
+pub struct PlaySoundEffect(pub SoundKind);
+
 /// Marker for a sound effect that gets played when certain things happen, like pacman
-/// eating dots. Has a timer on it to despawn it later.
-#[derive(Component, Reflect)]
-pub struct SoundEffect {
-    timer: Timer
+/// eating dots. The entity despawns itself when the sound finished playing.
+#[derive(Component, Reflect, Deref, Copy, Clone)]
+#[reflect(Component)]
+pub struct SoundEffect(pub SoundKind);
+
+/// All sound effects of the game.
+#[derive(Reflect, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SoundKind {
+    Start,
+    Waka,
+    FruitEaten,
+    GhostEaten,
+    HighScore,
+    Dying,
 }
 
-impl SoundEffect {
-    /// Create a new sound effect with a timer of one second.
-    pub fn new(duration_secs: u64) -> Self {
-        SoundEffect {
-            timer: Timer::new(Duration::from_secs(duration_secs), TimerMode::Once)
+impl SoundKind {
+    pub fn path(&self) -> &'static str {
+        match self {
+            SoundKind::Start => assets::sounds::START_OGG,
+            SoundKind::Waka => assets::sounds::WAKA_OGG,
+            SoundKind::FruitEaten => assets::sounds::FRUIT_EATEN_OGG,
+            SoundKind::GhostEaten => assets::sounds::GHOST_EATEN_OGG,
+            SoundKind::HighScore => assets::sounds::HIGH_SCORE_OGG,
+            SoundKind::Dying => assets::sounds::DYING_OGG,
         }
     }
 
-    pub fn update(&mut self, delta: Duration) {
-        self.timer.tick(delta);
+    /// How many sounds of this kind can play at the same time.
+    pub fn max_concurrent(&self) -> usize {
+        match self {
+            SoundKind::FruitEaten | SoundKind::GhostEaten => 2,
+            _ => 1,
+        }
     }
 
-    pub fn finished(&self) -> bool {
-        self.timer.is_finished()
+    /// A sound silences all playing sounds with a lower priority and is not played while
+    /// a sound with a higher priority plays.
+    pub fn priority(&self) -> u8 {
+        match self {
+            SoundKind::Dying => 1,
+            _ => 0,
+        }
     }
-}
\ No newline at end of file
+
+    /// If a sound of this kind should be played once the current one finished, instead of being dropped
+    /// when too many are already playing.
+    ///
+    /// Pacman eats dots faster than the waka plays, so without waiting, the waka would make pauses.
+    pub fn waits_when_busy(&self) -> bool {
+        matches!(self, SoundKind::Waka)
+    }
+}
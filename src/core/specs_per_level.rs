@@ -1,5 +1,4 @@
 use crate::core::prelude::*;
-use crate::core::prelude::{Fruit, Level};
 use bevy::prelude::*;
 use std::collections::HashMap;
 
@@ -27,7 +26,6 @@ impl Default for SpecsPerLevel {
                 (
                     1,
                     Spec {
-                        fruit_to_spawn: Cherry,
                         pacman_normal_speed_modifier: 0.8,
                         pacman_frightened_speed_modifier: 0.9,
                         ghost_normal_speed_modifier: 0.75,
@@ -43,7 +41,6 @@ impl Default for SpecsPerLevel {
                 (
                     2,
                     Spec {
-                        fruit_to_spawn: Strawberry,
                         pacman_normal_speed_modifier: 0.9,
                         pacman_frightened_speed_modifier: 0.95,
                         ghost_normal_speed_modifier: 0.85,
@@ -59,7 +56,6 @@ impl Default for SpecsPerLevel {
                 (
                     3,
                     Spec {
-                        fruit_to_spawn: Peach,
                         pacman_normal_speed_modifier: 0.9,
                         pacman_frightened_speed_modifier: 0.95,
                         ghost_normal_speed_modifier: 0.85,
@@ -75,7 +71,6 @@ impl Default for SpecsPerLevel {
                 (
                     4,
                     Spec {
-                        fruit_to_spawn: Peach,
                         pacman_normal_speed_modifier: 0.9,
                         ghost_normal_speed_modifier: 0.85,
                         ghost_tunnel_speed_modifier: 0.45,
@@ -91,7 +86,6 @@ impl Default for SpecsPerLevel {
                 (
                     5,
                     Spec {
-                        fruit_to_spawn: Apple,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -107,7 +101,6 @@ impl Default for SpecsPerLevel {
                 (
                     6,
                     Spec {
-                        fruit_to_spawn: Apple,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -123,7 +116,6 @@ impl Default for SpecsPerLevel {
                 (
                     7,
                     Spec {
-                        fruit_to_spawn: Grapes,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -139,7 +131,6 @@ impl Default for SpecsPerLevel {
                 (
                     8,
                     Spec {
-                        fruit_to_spawn: Grapes,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -155,7 +146,6 @@ impl Default for SpecsPerLevel {
                 (
                     9,
                     Spec {
-                        fruit_to_spawn: Galaxian,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -171,7 +161,6 @@ impl Default for SpecsPerLevel {
                 (
                     10,
                     Spec {
-                        fruit_to_spawn: Galaxian,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -187,7 +176,6 @@ impl Default for SpecsPerLevel {
                 (
                     11,
                     Spec {
-                        fruit_to_spawn: Bell,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -203,7 +191,6 @@ impl Default for SpecsPerLevel {
                 (
                     12,
                     Spec {
-                        fruit_to_spawn: Bell,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -219,7 +206,6 @@ impl Default for SpecsPerLevel {
                 (
                     13,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -235,7 +221,6 @@ impl Default for SpecsPerLevel {
                 (
                     14,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -251,7 +236,6 @@ impl Default for SpecsPerLevel {
                 (
                     15,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -267,7 +251,6 @@ impl Default for SpecsPerLevel {
                 (
                     16,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -283,7 +266,6 @@ impl Default for SpecsPerLevel {
                 (
                     17,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -299,7 +281,6 @@ impl Default for SpecsPerLevel {
                 (
                     18,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -315,7 +296,6 @@ impl Default for SpecsPerLevel {
                 (
                     19,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -331,7 +311,6 @@ impl Default for SpecsPerLevel {
                 (
                     20,
                     Spec {
-                        fruit_to_spawn: Key,
                         pacman_normal_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 0.95,
                         ghost_tunnel_speed_modifier: 0.5,
@@ -346,7 +325,6 @@ impl Default for SpecsPerLevel {
                 ),
             ],
             Spec {
-                fruit_to_spawn: Key,
                 pacman_normal_speed_modifier: 0.9,
                 pacman_frightened_speed_modifier: 0.9,
                 ghost_normal_speed_modifier: 0.95,
@@ -387,8 +365,6 @@ impl SpecsPerLevel {
 /// The difficulty specifications for a level
 #[derive(Reflect, Copy, Clone, Debug, Default, PartialEq)]
 pub struct Spec {
-    /// What fruit to spawn
-    pub fruit_to_spawn: Fruit,
     /// Modifier for pacmans speed if no energizer is active
     pub pacman_normal_speed_modifier: f32,
     /// Modifier for pacmans speed if an energizer is active
@@ -411,6 +387,22 @@ pub struct Spec {
     pub frightened_time: f32,
 }
 
+impl Spec {
+    /// Return the elroy phase for the given number of remaining dots: 0 for none, 1 or 2 else.
+    pub fn elroy_phase(
+        &self,
+        remaining_dots: usize,
+    ) -> usize {
+        if remaining_dots <= self.elroy_2_dots_left {
+            2
+        } else if remaining_dots <= self.elroy_1_dots_left {
+            1
+        } else {
+            0
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::prelude::*;
@@ -423,7 +415,6 @@ mod tests {
                 (
                     1,
                     Spec {
-                        fruit_to_spawn: Cherry,
                         pacman_normal_speed_modifier: 1.0,
                         pacman_frightened_speed_modifier: 1.0,
                         ghost_normal_speed_modifier: 1.0,
@@ -439,7 +430,6 @@ mod tests {
                 (
                     2,
                     Spec {
-                        fruit_to_spawn: Apple,
                         pacman_normal_speed_modifier: 2.0,
                         pacman_frightened_speed_modifier: 2.0,
                         ghost_normal_speed_modifier: 2.0,
@@ -474,7 +464,6 @@ mod tests {
     #[test]
     fn it_returns_a_default_spec_when_none_is_registered_for_the_given_level() {
         let spec = Spec {
-            fruit_to_spawn: Cherry,
             pacman_normal_speed_modifier: 1.0,
             pacman_frightened_speed_modifier: 1.0,
             ghost_normal_speed_modifier: 1.0,
@@ -493,4 +482,19 @@ mod tests {
 
         assert_eq!(retrieved_spec, &specs_per_level.default)
     }
+
+    #[test]
+    fn the_elroy_phase_depends_on_the_remaining_dots() {
+        let spec = Spec {
+            elroy_1_dots_left: 20,
+            elroy_2_dots_left: 10,
+            ..Spec::default()
+        };
+
+        assert_eq!(spec.elroy_phase(21), 0);
+        assert_eq!(spec.elroy_phase(20), 1);
+        assert_eq!(spec.elroy_phase(11), 1);
+        assert_eq!(spec.elroy_phase(10), 2);
+        assert_eq!(spec.elroy_phase(0), 2);
+    }
 }
@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
+
+use crate::core::prelude::*;
+use crate::sprite_sheet::{SpriteSheet, SpriteSheets};
+
+pub(super) struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(RonAssetPlugin::<Theme>::new(&["theme.ron"]))
+            .register_type::<Theme>()
+            .register_type::<ThemeSprite>()
+            .init_resource::<ThemePath>()
+            .add_systems(OnEnter(Setup(CreateSpriteSheets)), insert_active_theme)
+            .add_systems(Update, (
+                report_incomplete_themes,
+                report_broken_themes.run_if(resource_added::<SpriteSheets>),
+            ))
+        ;
+    }
+}
+
+/// The asset path of the theme the game starts with.
+#[derive(Resource, Deref)]
+pub struct ThemePath(pub String);
+
+impl Default for ThemePath {
+    fn default() -> Self {
+        ThemePath(DEFAULT_THEME_PATH.to_string())
+    }
+}
+
+/// A skin of the game. Maps the names of all sprites the game uses (like "pacman_dying")
+/// to the textures and animation timings they are shown with.
+///
+/// Every theme is preloaded like all other assets, so the theme can be switched at any time.
+/// The active theme is inserted as resource.
+#[derive(Asset, Resource, Reflect, Deserialize, Clone)]
+pub struct Theme {
+    /// The name shown in the options
+    pub name: String,
+    pub sprites: HashMap<String, ThemeSprite>,
+}
+
+impl Theme {
+    pub fn sprite(&self, name: &str) -> Option<&ThemeSprite> {
+        self.sprites.get(name)
+    }
+
+    /// Return the names of all sprites the game uses which this theme does not define.
+    pub fn missing_sprites(&self, ghost_behaviours: &GhostBehaviours) -> Vec<String> {
+        required_sprites(ghost_behaviours)
+            .into_iter()
+            .filter(|name| !self.sprites.contains_key(name))
+            .collect()
+    }
+
+    /// Return if the theme defines every sprite the game uses, so it can be played with.
+    pub fn is_complete(&self, ghost_behaviours: &GhostBehaviours) -> bool {
+        self.missing_sprites(ghost_behaviours).is_empty()
+    }
+
+    /// Return the names of all sprites which cannot be shown, because their sprite sheet was not loaded or
+    /// they use frames the sheet does not have, together with the reason.
+    pub fn broken_sprites(&self, sprite_sheets: &SpriteSheets) -> Vec<String> {
+        let mut broken = self
+            .sprites
+            .iter()
+            .filter(|(_, sprite)| !sprite.is_single_image())
+            .filter_map(|(name, sprite)| sprite.sheet(sprite_sheets).err().map(|e| format!("{name} ({e})")))
+            .collect::<Vec<_>>();
+        broken.sort();
+        broken
+    }
+}
+
+/// The walls of the maze and the ghost house. Every wall also needs a blinking variant.
+const WALL_SPRITES: [&str; 6] = [
+    "outer_wall",
+    "outer_wall_corner",
+    "inner_wall",
+    "inner_wall_corner",
+    "ghost_house_wall",
+    "ghost_house_wall_corner",
+];
+
+/// Every other sprite the game uses, except the fruits and the sprites of the ghosts.
+const OTHER_SPRITES: [&str; 15] = [
+    "pacman_eating_up",
+    "pacman_eating_down",
+    "pacman_eating_left",
+    "pacman_eating_right",
+    "pacman_dying",
+    "pacman_life",
+    "ghost_eaten_up",
+    "ghost_eaten_down",
+    "ghost_eaten_left",
+    "ghost_eaten_right",
+    "ghost_frightened",
+    "ghost_frightened_blinking",
+    "ghost_house_entrance",
+    "dot",
+    "energizer",
+];
+
+/// Return the names of all sprites a theme must define, including the sprites of every registered ghost, ordered by name.
+fn required_sprites(ghost_behaviours: &GhostBehaviours) -> Vec<String> {
+    let walls = WALL_SPRITES
+        .into_iter()
+        .flat_map(|wall| [wall.to_string(), format!("{wall}_blinking")]);
+    let fruits = [Cherry, Strawberry, Peach, Apple, Grapes, Galaxian, Bell, Key]
+        .iter()
+        .map(|fruit| get_sprite_name_for_fruit(fruit).to_string());
+    let ghosts = ghost_behaviours
+        .by_release_priority()
+        .into_iter()
+        .flat_map(|ghost| ghost_behaviours.get(&ghost).textures)
+        .map(str::to_string);
+
+    let mut sprites = walls
+        .chain(fruits)
+        .chain(ghosts)
+        .chain(OTHER_SPRITES.into_iter().map(str::to_string))
+        .collect::<Vec<_>>();
+    sprites.sort();
+    sprites.dedup();
+    sprites
+}
+
+/// The textures of a single sprite, which might be animated.
+#[derive(Reflect, Deserialize, Clone)]
+pub struct ThemeSprite {
+    /// The path of a single image (like "textures/dot.png") or of a sprite sheet without file
+    /// ending (like "textures/pacman/pacman_dying", see [SpriteSheets::get_sheet]).
+    pub path: String,
+    /// The tag of the sprite sheet the animation consists of, as named in aseprite.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// The frames of the sprite sheet the animation consists of, if no tag is given. Every frame is used if empty.
+    #[serde(default)]
+    pub frames: Vec<usize>,
+    /// How long one iteration of the animation takes, spread evenly over all frames. If not set,
+    /// every frame is shown as long as authored in the sprite sheet.
+    #[serde(default)]
+    pub duration_secs: f32,
+    #[serde(default)]
+    pub repeating: bool,
+}
+
+impl ThemeSprite {
+    fn is_single_image(&self) -> bool {
+        Path::new(&self.path).extension().is_some()
+    }
+
+    /// Return the sprite sheet of this sprite, or why it cannot be used: it was not loaded or
+    /// it does not have every frame of the sprite.
+    fn sheet<'a>(&self, sprite_sheets: &'a SpriteSheets) -> Result<&'a SpriteSheet, String> {
+        let Some(sheet) = sprite_sheets.get_sheet(&self.path) else {
+            return Err(format!("the sprite sheet {} was not loaded", self.path));
+        };
+
+        match self.frames.iter().find(|index| **index >= sheet.frame_count()) {
+            Some(index) => Err(format!("the sprite sheet {} has no frame {index}, only {}", self.path, sheet.frame_count())),
+            None => Ok(sheet),
+        }
+    }
+}
+
+/// The animations of an entity by the names of their theme sprites. Used to recreate
+/// the animations when the theme changes.
+#[derive(Component, Clone)]
+pub struct ThemedAnimations {
+    /// The name of the animation and the name of its theme sprite
+    animations: Vec<(String, String)>,
+    initial: String,
+}
+
+impl ThemedAnimations {
+    pub fn new<A: ToString, S: ToString>(
+        animations: impl IntoIterator<Item = (A, S)>,
+        initial: impl ToString,
+    ) -> Self {
+        ThemedAnimations {
+            animations: animations
+                .into_iter()
+                .map(|(a, s)| (a.to_string(), s.to_string()))
+                .collect(),
+            initial: initial.to_string(),
+        }
+    }
+}
+
+/// The name of the theme sprite a not animated entity (or UI image) is shown with.
+#[derive(Component, Deref, Clone)]
+pub struct ThemedImage(pub String);
+
+/// Everything needed to create images and animations from the active theme.
+#[derive(SystemParam)]
+pub struct ThemeAssets<'w> {
+    theme: Res<'w, Theme>,
+    asset_server: Res<'w, AssetServer>,
+    sprite_sheets: Res<'w, SpriteSheets>,
+}
+
+impl ThemeAssets<'_> {
+    /// Return a sprite showing the first frame of the theme sprite with the given name.
+    pub fn sprite(&self, name: &str) -> Sprite {
+        self.animation(name).sprite()
+    }
+
+    /// Return an UI image showing the first frame of the theme sprite with the given name.
+    pub fn image_node(&self, name: &str) -> ImageNode {
+        self.animation(name).image_node()
+    }
+
+    /// Return the animation of the theme sprite with the given name. If the theme has no such sprite
+    /// or it cannot be shown, nothing is shown.
+    pub fn animation(&self, name: &str) -> Animation {
+        let Some(sprite) = self.theme.sprite(name) else {
+            warn!("the theme {} has no sprite {name}", self.theme.name);
+            return Animation::from_texture(default());
+        };
+
+        if sprite.is_single_image() {
+            return Animation::from_texture(self.asset_server.load(sprite.path.clone()));
+        }
+
+        let sheet = match sprite.sheet(&self.sprite_sheets) {
+            Ok(sheet) => sheet,
+            Err(e) => {
+                warn!("the sprite {name} of the theme {} cannot be shown: {e}", self.theme.name);
+                return Animation::from_texture(default());
+            }
+        };
+        let frames = frames(sprite, sheet);
+
+        match (frames.len(), sprite.duration_secs > 0.0) {
+            (1, _) => sheet.still(frames[0]),
+            (_, true) => sheet.evenly_timed_animation(frames, sprite.duration_secs, sprite.repeating),
+            (_, false) => sheet.animation(frames, sprite.repeating),
+        }
+    }
+
+    pub fn animations(&self, themed: &ThemedAnimations) -> Animations {
+        Animations::new(
+            themed
+                .animations
+                .iter()
+                .map(|(animation, sprite)| (animation, self.animation(sprite))),
+            &themed.initial,
+        )
+    }
+
+}
+
+/// Return the indexes of the sheet frames the given sprite consists of.
+/// If the tag of the sprite does not exist in the sheet, every frame is used.
+fn frames(sprite: &ThemeSprite, sheet: &SpriteSheet) -> Vec<usize> {
+    match (&sprite.tag, sprite.frames.is_empty()) {
+        (Some(tag), _) => sheet.tag_frames(tag).unwrap_or_else(|| {
+            warn!("the sprite sheet {} has no tag {tag}, using all of its frames", sprite.path);
+            (0..sheet.frame_count()).collect()
+        }),
+        (None, false) => sprite.frames.clone(),
+        (None, true) => (0..sheet.frame_count()).collect(),
+    }
+}
+
+/// Insert the theme to start with. If it was not loaded or misses sprites, the default theme is used instead.
+fn insert_active_theme(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme_path: Res<ThemePath>,
+    themes: Res<Assets<Theme>>,
+    ghost_behaviours: Res<GhostBehaviours>,
+) {
+    let theme = [theme_path.as_str(), DEFAULT_THEME_PATH]
+        .into_iter()
+        .find_map(|path| complete_theme(path, &asset_server, &themes, &ghost_behaviours));
+
+    match theme {
+        Some(theme) => commands.insert_resource(theme.clone()),
+        None => {
+            error!("neither the theme {} nor the default theme can be used, no sprites will be shown", **theme_path);
+            commands.insert_resource(Theme {
+                name: theme_path.0.clone(),
+                sprites: HashMap::new(),
+            });
+        }
+    }
+}
+
+/// Return the theme with the given path if it was loaded and defines every sprite. Otherwise, log why it can't be used.
+fn complete_theme<'a>(
+    path: &str,
+    asset_server: &AssetServer,
+    themes: &'a Assets<Theme>,
+    ghost_behaviours: &GhostBehaviours,
+) -> Option<&'a Theme> {
+    let Some(theme) = themes.get(&asset_server.load::<Theme>(path)) else {
+        error!("the theme {path} was not loaded");
+        return None;
+    };
+
+    match theme.is_complete(ghost_behaviours) {
+        true => Some(theme),
+        false => {
+            error!("the theme {path} cannot be used, it has no sprites {}", theme.missing_sprites(ghost_behaviours).join(", "));
+            None
+        }
+    }
+}
+
+/// Tell which loaded themes miss sprites. These themes cannot be selected in the options.
+fn report_incomplete_themes(
+    mut theme_events: MessageReader<AssetEvent<Theme>>,
+    themes: Res<Assets<Theme>>,
+    ghost_behaviours: Res<GhostBehaviours>,
+) {
+    for event in theme_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+
+        let Some(theme) = themes.get(*id) else {
+            continue;
+        };
+
+        let missing_sprites = theme.missing_sprites(&ghost_behaviours);
+
+        if !missing_sprites.is_empty() {
+            warn!("the theme {} cannot be selected, it has no sprites {}", theme.name, missing_sprites.join(", "));
+        }
+    }
+}
+
+/// Tell which loaded themes have sprites which cannot be shown. This is only known once the sprite sheets
+/// exist. These themes cannot be selected in the options either.
+fn report_broken_themes(
+    themes: Res<Assets<Theme>>,
+    sprite_sheets: Res<SpriteSheets>,
+) {
+    for (_, theme) in themes.iter() {
+        let broken_sprites = theme.broken_sprites(&sprite_sheets);
+
+        if !broken_sprites.is_empty() {
+            warn!("the theme {} cannot be selected, these sprites cannot be shown: {}", theme.name, broken_sprites.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use bevy::asset::ron;
+    use bevy::prelude::*;
+
+    use crate::core::prelude::*;
+    use crate::core::theme::ThemeSprite;
+    use crate::sprite_sheet::{SpriteSheet, SpriteSheets};
+
+    fn load_theme(path: &str) -> Theme {
+        let theme = std::fs::read_to_string(Path::new("assets").join(path)).expect("the theme should exist");
+        ron::from_str(&theme).expect("the theme should be valid")
+    }
+
+    #[test]
+    fn the_default_theme_defines_every_sprite_the_game_uses() {
+        let theme = load_theme(DEFAULT_THEME_PATH);
+
+        assert_eq!(theme.missing_sprites(&GhostBehaviours::default()), Vec::<String>::new());
+    }
+
+    #[test]
+    fn a_theme_without_all_sprites_is_incomplete() {
+        let mut theme = load_theme(DEFAULT_THEME_PATH);
+        theme.sprites.remove("dot");
+        theme.sprites.remove("inner_wall_blinking");
+
+        assert_eq!(theme.missing_sprites(&GhostBehaviours::default()), vec!["dot", "inner_wall_blinking"]);
+        assert!(!theme.is_complete(&GhostBehaviours::default()));
+
+        let empty = Theme {
+            name: "empty".to_string(),
+            sprites: HashMap::new(),
+        };
+        assert!(empty.missing_sprites(&GhostBehaviours::default()).contains(&"blinky_up".to_string()));
+    }
+
+    /// A theme with the given sprites (name, path and frames) and the sprite sheet "textures/sheet" with two frames
+    fn theme_with_sprites(sprites: impl IntoIterator<Item = (&'static str, &'static str, Vec<usize>)>) -> (Theme, SpriteSheets) {
+        let aseprite_data = serde_json::from_str(r#"{
+            "frames": [
+                { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 } },
+                { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 } }
+            ]
+        }"#).expect("the aseprite data should be valid");
+        let sprite_sheets = SpriteSheets::new([(
+            "textures/sheet".to_string(),
+            SpriteSheet::new(Handle::default(), Handle::default(), &aseprite_data),
+        )]);
+        let theme = Theme {
+            name: "test".to_string(),
+            sprites: sprites
+                .into_iter()
+                .map(|(name, path, frames)| (name.to_string(), ThemeSprite {
+                    path: path.to_string(),
+                    tag: None,
+                    frames,
+                    duration_secs: 0.0,
+                    repeating: false,
+                }))
+                .collect(),
+        };
+
+        (theme, sprite_sheets)
+    }
+
+    #[test]
+    fn sprites_with_an_unknown_sheet_or_frame_are_broken() {
+        let (theme, sprite_sheets) = theme_with_sprites([
+            ("fine", "textures/sheet", vec![0, 1]),
+            ("all_frames", "textures/sheet", vec![]),
+            ("image", "textures/dot.png", vec![]),
+            ("unknown_frame", "textures/sheet", vec![1, 2]),
+            ("unknown_sheet", "textures/mistyped", vec![0]),
+        ]);
+
+        assert_eq!(theme.broken_sprites(&sprite_sheets), vec![
+            "unknown_frame (the sprite sheet textures/sheet has no frame 2, only 2)",
+            "unknown_sheet (the sprite sheet textures/mistyped was not loaded)",
+        ]);
+    }
+
+    #[test]
+    fn sprites_which_fit_their_sheet_are_not_broken() {
+        let (theme, sprite_sheets) = theme_with_sprites([("fine", "textures/sheet", vec![1])]);
+
+        assert!(theme.broken_sprites(&sprite_sheets).is_empty());
+    }
+}
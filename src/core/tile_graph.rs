@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+/// Graph of all tiles in the maze which are not blocked by a wall.
+///
+/// Two tiles are connected if they are direct neighbours or if they are tunnels
+/// with the same index. Used by everything that has to find its way through the maze
+/// on its own, like wandering fruits.
+#[derive(Resource, Clone, Default)]
+pub struct TileGraph {
+    walkable: HashSet<Pos>,
+    tunnel_links: HashMap<Pos, (Pos, Dir)>,
+}
+
+impl TileGraph {
+    /// Create the graph for every tile of the given map.
+    ///
+    /// The tunnels are provided as their position, index and direction. A tunnel is linked
+    /// to every other tunnel with the same index.
+    pub fn new(
+        map: &Map,
+        walls: impl IntoIterator<Item = Pos>,
+        tunnels: impl IntoIterator<Item = (Pos, usize, Dir)>,
+    ) -> Self {
+        let walls = walls.into_iter().collect::<HashSet<_>>();
+        let walkable = map
+            .columns()
+            .flat_map(|x| map.rows().map(move |y| Pos::new(x, y)))
+            .filter(|pos| !walls.contains(pos))
+            .collect();
+
+        let tunnels = tunnels.into_iter().collect::<Vec<_>>();
+        let tunnel_links = tunnels
+            .iter()
+            .flat_map(|(pos_a, index_a, dir_a)| {
+                tunnels
+                    .iter()
+                    .filter(move |(pos_b, index_b, _)| pos_a != pos_b && index_a == index_b)
+                    .map(move |(pos_b, _, _)| (*pos_a, (*pos_b, *dir_a)))
+            })
+            .collect();
+
+        TileGraph {
+            walkable,
+            tunnel_links,
+        }
+    }
+
+    pub fn is_walkable(
+        &self,
+        pos: &Pos,
+    ) -> bool {
+        self.walkable.contains(pos)
+    }
+
+    /// Return all walkable tiles which can be reached in one step from the given position,
+    /// together with the direction of the step.
+    pub fn neighbours(
+        &self,
+        pos: Pos,
+    ) -> Vec<(Pos, Dir)> {
+        let mut neighbours = pos
+            .neighbours_with_directions()
+            .into_iter()
+            .filter(|(neighbour, _)| self.is_walkable(neighbour))
+            .collect::<Vec<_>>();
+
+        if let Some(link) = self.tunnel_links.get(&pos) {
+            neighbours.push(*link)
+        }
+
+        neighbours
+    }
+
+    /// Return the amount of steps from every reachable tile to the nearest of the given sources.
+    pub fn distance_field(
+        &self,
+        sources: impl IntoIterator<Item = Pos>,
+    ) -> HashMap<Pos, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        for source in sources {
+            if self.is_walkable(&source) && !distances.contains_key(&source) {
+                distances.insert(source, 0);
+                queue.push_back(source);
+            }
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            let distance = distances[&pos];
+
+            for (neighbour, _) in self.neighbours(pos) {
+                if !distances.contains_key(&neighbour) {
+                    distances.insert(neighbour, distance + 1);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Return the shortest path between two tiles, including both start and goal.
+    /// Returns None if the goal cannot be reached.
+    pub fn shortest_path(
+        &self,
+        from: Pos,
+        to: Pos,
+    ) -> Option<Vec<Pos>> {
+        let distances = self.distance_field([to]);
+        let mut current = from;
+        let mut path = vec![current];
+
+        while current != to {
+            let current_distance = *distances.get(&current)?;
+            current = self
+                .neighbours(current)
+                .into_iter()
+                .map(|(neighbour, _)| neighbour)
+                .find(|neighbour| distances.get(neighbour) == Some(&(current_distance - 1)))?;
+            path.push(current);
+        }
+
+        Some(path)
+    }
+}
+
+/// The amount of steps from every tile to the tiles in front of the ghost house entrance.
+///
+/// Calculated once when the game starts, so eaten ghosts can take the shortest way home on any map.
+#[derive(Resource, Deref, Clone, Default)]
+pub struct HomeDistanceField(pub HashMap<Pos, usize>);
+
+impl HomeDistanceField {
+    /// Return the direction of the step that brings an entity at the given position nearest to home.
+    ///
+    /// Like every ghost movement, the step never goes back in the direction the entity came from.
+    /// Returns None if the position is not part of the field or there is no other way than back.
+    pub fn next_step(
+        &self,
+        tile_graph: &TileGraph,
+        pos: Pos,
+        direction: Dir,
+    ) -> Option<Dir> {
+        self.0.get(&pos)?;
+
+        tile_graph
+            .neighbours(pos)
+            .into_iter()
+            .filter(|(_, dir)| *dir != direction.opposite())
+            .filter_map(|(neighbour, dir)| self.0.get(&neighbour).map(|distance| (dir, *distance)))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(dir, _)| dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::prelude::*;
+
+    /// A 5x3 map where the middle row is open and the bottom and top row are walls.
+    /// Like every map, its rows go from 1 to 3. Tiles (0, 2) and (4, 2) are linked tunnels.
+    fn corridor() -> TileGraph {
+        let walls = (0..5)
+            .flat_map(|x| [Pos::new(x, 1), Pos::new(x, 3)])
+            .collect::<Vec<_>>();
+
+        TileGraph::new(
+            &Map { width: 5, height: 3 },
+            walls,
+            [(Pos::new(0, 2), 0, Left), (Pos::new(4, 2), 0, Right)],
+        )
+    }
+
+    #[test]
+    fn walls_are_not_walkable() {
+        let graph = corridor();
+
+        assert!(graph.is_walkable(&Pos::new(2, 2)));
+        assert!(!graph.is_walkable(&Pos::new(2, 1)));
+    }
+
+    #[test]
+    fn tunnels_are_neighbours() {
+        let graph = corridor();
+
+        assert!(graph.neighbours(Pos::new(0, 2)).contains(&(Pos::new(4, 2), Left)))
+    }
+
+    #[test]
+    fn the_distance_field_uses_tunnels() {
+        let graph = corridor();
+
+        let distances = graph.distance_field([Pos::new(0, 2)]);
+
+        assert_eq!(distances[&Pos::new(4, 2)], 1);
+        assert_eq!(distances[&Pos::new(2, 2)], 2);
+    }
+
+    #[test]
+    fn the_shortest_path_contains_start_and_goal() {
+        let graph = corridor();
+
+        let path = graph.shortest_path(Pos::new(1, 2), Pos::new(3, 2)).unwrap();
+
+        assert_eq!(path, vec![Pos::new(1, 2), Pos::new(2, 2), Pos::new(3, 2)])
+    }
+
+    #[test]
+    fn the_next_step_home_never_turns_around() {
+        let graph = corridor();
+        let field = HomeDistanceField(graph.distance_field([Pos::new(1, 2)]));
+
+        assert_eq!(field.next_step(&graph, Pos::new(2, 2), Left), Some(Left));
+        assert_eq!(field.next_step(&graph, Pos::new(2, 2), Right), Some(Right));
+    }
+
+    #[test]
+    fn there_is_no_path_into_walls() {
+        let graph = corridor();
+
+        assert!(graph.shortest_path(Pos::new(1, 2), Pos::new(1, 1)).is_none())
+    }
+
+    #[test]
+    fn tiles_outside_of_the_map_are_not_walkable() {
+        let graph = TileGraph::new(&Map { width: 2, height: 2 }, Vec::<Pos>::new(), Vec::<(Pos, usize, Dir)>::new());
+
+        assert!(graph.is_walkable(&Pos::new(0, 1)));
+        assert!(graph.is_walkable(&Pos::new(1, 2)));
+        assert!(!graph.is_walkable(&Pos::new(0, 0)));
+        assert!(!graph.is_walkable(&Pos::new(0, 3)));
+        assert!(!graph.is_walkable(&Pos::new(2, 1)));
+    }
+}
@@ -13,11 +13,14 @@ impl Plugin for DebugPlugin {
         app
             .add_plugins(EguiPlugin::default())
             .add_plugins(WorldInspectorPlugin::new())
+            .init_resource::<DebugOverlay>()
             .add_systems(
                 Update,
                 (
                     toggle_time,
-                    despawn_all_edibles
+                    despawn_all_edibles,
+                    toggle_debug_overlay,
+                    draw_debug_overlay.run_if(|overlay: Res<DebugOverlay>| **overlay)
                 )
             )
         ;
@@ -54,4 +57,109 @@ fn despawn_all_edibles(
             commands.entity(e).despawn();
         }
     }
-}
\ No newline at end of file
+}
+
+/// If the debug overlay is visible. The overlay shows where the ghosts want to go and the special tiles of the maze.
+#[cfg(debug_assertions)]
+#[derive(Resource, Deref, DerefMut, Default)]
+struct DebugOverlay(bool);
+
+#[cfg(debug_assertions)]
+fn toggle_debug_overlay(
+    mut overlay: ResMut<DebugOverlay>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyO) {
+        **overlay = !**overlay;
+    }
+}
+
+/// Draw the debug overlay:
+/// - one way tiles in white and tunnel hallways in dark grey
+/// - for every ghost, the tile it finally wants to reach (its chase or scatter target or the ghost house entrance)
+///   in the color of the ghost, together with the helpers the chase target depends on
+/// - for every ghost, the next tile it moves to, colored by its state
+#[cfg(debug_assertions)]
+#[allow(clippy::too_many_arguments)]
+fn draw_debug_overlay(
+    mut gizmos: Gizmos,
+    ghost_behaviours: Res<GhostBehaviours>,
+    pacman_query: Query<(&Transform, &Dir), With<Pacman>>,
+    ghost_query: Query<(&Ghost, &GhostState, &Transform, &Target)>,
+    corner_query: Query<(&GhostCorner, &Tiles)>,
+    ghost_house_query: Query<&GhostHouseLayout>,
+    one_way_query: Query<&Tiles, With<OneWay>>,
+    hallway_query: Query<&Tiles, With<TunnelHallway>>,
+) {
+    let tile_size = Vec2::splat(FIELD_SIZE);
+
+    for tiles in &one_way_query {
+        gizmos.rect_2d(tiles.to_vec3(0.0).truncate(), tile_size, Color::WHITE);
+    }
+
+    for tiles in &hallway_query {
+        gizmos.rect_2d(tiles.to_vec3(0.0).truncate(), tile_size, Color::srgb(0.3, 0.3, 0.3));
+    }
+
+    let Ok((pacman_transform, pacman_direction)) = pacman_query.single() else {
+        return;
+    };
+    let ghost_positions = ghost_query
+        .iter()
+        .map(|(ghost, _, transform, _)| (*ghost, Pos::from_vec3(transform.translation)))
+        .collect::<Vec<_>>();
+
+    for (ghost, state, transform, target) in &ghost_query {
+        let behaviour = ghost_behaviours.get(ghost);
+        let ghost_coordinates = transform.translation.truncate();
+
+        if target.is_set() {
+            let next_tile = target.get().truncate();
+            gizmos.line_2d(ghost_coordinates, next_tile, behaviour.color);
+            gizmos.rect_2d(next_tile, tile_size * 0.8, state_color(state));
+        }
+
+        let scatter_position = corner_query
+            .iter()
+            .find(|(corner, _)| ***corner == behaviour.scatter_corner)
+            .map(|(_, tiles)| tiles.to_pos());
+        let Some(scatter_position) = scatter_position else {
+            continue;
+        };
+        let context = ChaseContext {
+            ghost: *ghost,
+            ghost_position: Pos::from_vec3(transform.translation),
+            pacman_position: Pos::from_vec3(pacman_transform.translation),
+            pacman_direction: *pacman_direction,
+            scatter_position,
+            ghost_positions: &ghost_positions,
+        };
+
+        let final_target = match state {
+            Chase => {
+                (behaviour.draw_chase_helpers)(&context, &mut gizmos, behaviour.color);
+                (behaviour.chase_target)(&context).to_vec3(0.0).truncate()
+            }
+            Scatter => scatter_position.to_vec3(0.0).truncate(),
+            Eaten => match ghost_house_query.single() {
+                Ok(ghost_house) => ghost_house.entrance_coordinates.truncate(),
+                Err(_) => continue,
+            },
+            Frightened | Spawned => continue,
+        };
+
+        gizmos.cross_2d(final_target, FIELD_SIZE / 2.0, behaviour.color);
+        gizmos.rect_2d(final_target, tile_size, behaviour.color);
+    }
+}
+
+#[cfg(debug_assertions)]
+fn state_color(state: &GhostState) -> Color {
+    match state {
+        Chase => Color::srgb(1.0, 1.0, 0.0),
+        Scatter => Color::srgb(0.0, 1.0, 0.0),
+        Frightened => Color::srgb(0.2, 0.2, 1.0),
+        Eaten => Color::srgb(0.7, 0.7, 0.7),
+        Spawned => Color::srgb(1.0, 0.5, 0.0),
+    }
+}
@@ -0,0 +1,361 @@
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+use crate::headless::{create_manually_updated_app, PreloadFailed};
+
+/// How many frames are played with the same action by default.
+const DEFAULT_FRAMES_PER_STEP: usize = 4;
+
+/// How many frames the game may take to get to a state the agent can act in (one minute of game time).
+/// The pauses of the game are only a few seconds long, so the game is stuck if it takes longer.
+const MAX_FRAMES_UNTIL_PLAYABLE: usize = 60 * 60;
+
+/// An environment for reinforcement learning, which runs the real game headless with a fixed time step.
+///
+/// An agent controls pacman by passing an action to [PacmanEnv::step] and gets an observation of the maze,
+/// the reward for the step and if the game is over in return. Before the first step, [PacmanEnv::reset] must be called.
+pub struct PacmanEnv {
+    app: Option<App>,
+    frames_per_step: usize,
+    rewards: Rewards,
+}
+
+/// The reward for every gameplay message that happened during a step.
+#[derive(Resource, Copy, Clone, Debug)]
+pub struct Rewards {
+    pub dot_eaten: f32,
+    pub energizer_eaten: f32,
+    pub ghost_eaten: f32,
+    pub fruit_eaten: f32,
+    pub pacman_hit: f32,
+}
+
+impl Default for Rewards {
+    fn default() -> Self {
+        Rewards {
+            dot_eaten: 10.0,
+            energizer_eaten: 50.0,
+            ghost_eaten: 200.0,
+            fruit_eaten: 100.0,
+            pacman_hit: -500.0,
+        }
+    }
+}
+
+/// What the agent sees after a step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    pub width: usize,
+    pub height: usize,
+    /// The content of every tile, row by row, starting at the bottom left of the maze.
+    /// Use [Observation::tile] to access a single tile. The rows of the map start at 1,
+    /// so row y of the map is row y - 1 here.
+    pub tiles: Vec<TileContent>,
+    pub score: usize,
+    pub lives: usize,
+    pub level: usize,
+}
+
+impl Observation {
+    pub fn tile(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> TileContent {
+        self.tiles[y * self.width + x]
+    }
+}
+
+/// The most important thing on a tile. If multiple things are on one tile, ghosts come first,
+/// then pacman, then fruits, energizers and dots.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TileContent {
+    Empty,
+    Wall,
+    Dot,
+    Energizer,
+    Fruit,
+    Pacman,
+    Ghost(GhostState),
+}
+
+/// The result of a single step.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+}
+
+/// Why the environment cannot go on.
+#[derive(Clone, Debug)]
+pub enum EnvError {
+    PreloadFailed(PreloadFailed),
+    /// The game did not get running or over in time, but got stuck in the given state
+    Stuck(GameState),
+}
+
+impl std::fmt::Display for EnvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvError::PreloadFailed(e) => e.fmt(f),
+            EnvError::Stuck(state) => write!(f, "the game got stuck in the state {state:?}"),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+impl From<PreloadFailed> for EnvError {
+    fn from(e: PreloadFailed) -> Self {
+        EnvError::PreloadFailed(e)
+    }
+}
+
+/// The rewards collected since the last step.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct CollectedReward(f32);
+
+impl Default for PacmanEnv {
+    fn default() -> Self {
+        PacmanEnv::new()
+    }
+}
+
+impl PacmanEnv {
+    pub fn new() -> Self {
+        PacmanEnv {
+            app: None,
+            frames_per_step: DEFAULT_FRAMES_PER_STEP,
+            rewards: Rewards::default(),
+        }
+    }
+
+    /// Set how many frames are played with the same action.
+    pub fn with_frames_per_step(
+        mut self,
+        frames_per_step: usize,
+    ) -> Self {
+        self.frames_per_step = frames_per_step.max(1);
+        self
+    }
+
+    pub fn with_rewards(
+        mut self,
+        rewards: Rewards,
+    ) -> Self {
+        self.rewards = rewards;
+        self
+    }
+
+    /// Start a new game with the given seed and return the first observation.
+    ///
+    /// If the last game is over, the game just gets restarted. Otherwise, a whole new game is created,
+    /// which takes longer as all assets must be loaded again. Fails if the assets could not be loaded
+    /// or the game got stuck.
+    pub fn reset(
+        &mut self,
+        seed: u64,
+    ) -> Result<Observation, EnvError> {
+        if !self.app.as_ref().is_some_and(is_game_over) {
+            self.app = Some(create_app(seed, self.rewards)?);
+        }
+
+        let app = self.app.as_mut().expect("the app was just created");
+
+        if is_game_over(app) {
+            app.insert_resource(Random::from_seed(seed));
+            app.world_mut().write_message(GameWasRestarted);
+            update_while(app, is_game_over)?;
+        }
+
+        update_while(app, is_paused)?;
+        **app.world_mut().resource_mut::<CollectedReward>() = 0.0;
+        Ok(observe(app.world_mut()))
+    }
+
+    /// Let pacman move to the given direction for some frames. If the direction is None,
+    /// pacman continues to the last direction that could not be taken yet.
+    ///
+    /// Fails if the game got stuck.
+    pub fn step(
+        &mut self,
+        action: Option<Dir>,
+    ) -> Result<Step, EnvError> {
+        let app = self.app.as_mut().expect("reset must be called before the first step");
+
+        let mut wished_directions = app.world_mut().query_filtered::<&mut WishedDirection, With<Pacman>>();
+        for mut wished_direction in wished_directions.iter_mut(app.world_mut()) {
+            **wished_direction = action;
+        }
+
+        for _ in 0..self.frames_per_step {
+            app.update();
+
+            if !is_running(app) {
+                break;
+            }
+        }
+
+        // skip the pauses after pacman died or a level was finished, as the agent cannot do anything there
+        update_while(app, is_paused)?;
+
+        let reward = std::mem::take(&mut **app.world_mut().resource_mut::<CollectedReward>());
+
+        Ok(Step {
+            observation: observe(app.world_mut()),
+            reward,
+            done: is_game_over(app),
+        })
+    }
+}
+
+fn create_app(
+    seed: u64,
+    rewards: Rewards,
+) -> Result<App, PreloadFailed> {
+    create_manually_updated_app(|app| {
+        app.insert_resource(SelectedController(PacmanController::Remote))
+            .insert_resource(Random::from_seed(seed))
+            .insert_resource(rewards)
+            .init_resource::<CollectedReward>()
+            .add_systems(Update, collect_rewards.after(ProcessIntersectionsWithPacman));
+    })
+}
+
+fn collect_rewards(
+    rewards: Res<Rewards>,
+    mut collected_reward: ResMut<CollectedReward>,
+    mut dot_eaten_reader: MessageReader<DotWasEaten>,
+    mut energizer_eaten_reader: MessageReader<EnergizerWasEaten>,
+    mut ghost_eaten_reader: MessageReader<GhostWasEaten>,
+    mut fruit_eaten_reader: MessageReader<FruitWasEaten>,
+    mut pacman_hit_reader: MessageReader<PacmanWasHit>,
+) {
+    **collected_reward += dot_eaten_reader.read().count() as f32 * rewards.dot_eaten
+        + energizer_eaten_reader.read().count() as f32 * rewards.energizer_eaten
+        + ghost_eaten_reader.read().count() as f32 * rewards.ghost_eaten
+        + fruit_eaten_reader.read().count() as f32 * rewards.fruit_eaten;
+
+    // two ghosts might hit pacman in the same frame, but he only dies once
+    if pacman_hit_reader.read().count() > 0 {
+        **collected_reward += rewards.pacman_hit;
+    }
+}
+
+fn current_state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn is_running(app: &App) -> bool {
+    current_state(app) == Game(Running)
+}
+
+fn is_game_over(app: &App) -> bool {
+    current_state(app) == Game(GameOver)
+}
+
+/// Return if the game is neither running nor over, so the agent has to wait.
+fn is_paused(app: &App) -> bool {
+    !is_running(app) && !is_game_over(app)
+}
+
+/// Update the app as long as the given condition holds, but at most [MAX_FRAMES_UNTIL_PLAYABLE] times.
+fn update_while(
+    app: &mut App,
+    condition: impl Fn(&App) -> bool,
+) -> Result<(), EnvError> {
+    for _ in 0..MAX_FRAMES_UNTIL_PLAYABLE {
+        if !condition(app) {
+            return Ok(());
+        }
+
+        app.update();
+    }
+
+    match condition(app) {
+        true => Err(EnvError::Stuck(current_state(app))),
+        false => Ok(()),
+    }
+}
+
+fn observe(world: &mut World) -> Observation {
+    let map = world
+        .query::<&Map>()
+        .single(world)
+        .map(|map| Map { width: map.width, height: map.height })
+        .expect("the map should exist when the game runs");
+    let (width, height) = (map.width, map.height);
+    let ghosts = world
+        .query_filtered::<(&Transform, &GhostState), With<Ghost>>()
+        .iter(world)
+        .map(|(transform, state)| (vec![Pos::from_vec3(transform.translation)], TileContent::Ghost(*state)))
+        .collect::<Vec<_>>();
+
+    // later contents overwrite earlier ones, so the most important things come last
+    let layers = [
+        (positions_of::<Wall>(world), TileContent::Wall),
+        (positions_of::<Dot>(world), TileContent::Dot),
+        (positions_of::<Energizer>(world), TileContent::Energizer),
+        (positions_of::<Fruit>(world), TileContent::Fruit),
+        (positions_of::<Pacman>(world), TileContent::Pacman),
+    ];
+    let mut tiles = vec![TileContent::Empty; width * height];
+
+    for (positions, content) in layers.into_iter().chain(ghosts) {
+        for pos in positions {
+            if map.contains(&pos) {
+                let row = (pos.y() - map.rows().start()) as usize;
+                tiles[row * width + pos.x() as usize] = content;
+            }
+        }
+    }
+
+    Observation {
+        width,
+        height,
+        tiles,
+        score: **world.resource::<Score>(),
+        lives: **world.resource::<Lives>(),
+        level: **world.resource::<Level>(),
+    }
+}
+
+fn positions_of<C: Component>(world: &mut World) -> Vec<Pos> {
+    world
+        .query_filtered::<&Transform, With<C>>()
+        .iter(world)
+        .map(|transform| Pos::from_vec3(transform.translation))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::prelude::*;
+    use crate::env::{PacmanEnv, TileContent};
+
+    #[test]
+    fn an_agent_can_reset_and_step_through_a_game() {
+        let mut env = PacmanEnv::new();
+        let observation = env.reset(42).expect("the game should start");
+
+        assert_eq!(observation.score, 0);
+        assert_eq!(observation.level, 1);
+        assert_eq!((observation.width, observation.height), (28, 31));
+        assert_eq!(observation.tiles.len(), observation.width * observation.height);
+        assert!((0..observation.width).all(|x| observation.tile(x, 0) == TileContent::Wall), "the bottom row should be a wall");
+        assert!((0..observation.width).all(|x| observation.tile(x, observation.height - 1) == TileContent::Wall), "the top row should be a wall");
+        assert!(observation.tiles.contains(&TileContent::Pacman));
+        assert!(observation.tiles.contains(&TileContent::Dot));
+
+        let steps = (0..100)
+            .map(|_| env.step(Some(Left)).expect("the game should not get stuck"))
+            .collect::<Vec<_>>();
+
+        assert!(steps.iter().any(|step| step.reward > 0.0));
+        assert!(steps.iter().all(|step| step.observation.tiles.len() == observation.tiles.len()));
+
+        let observation = env.reset(42).expect("the game should start again");
+        assert_eq!(observation.level, 1);
+    }
+}
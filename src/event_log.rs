@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use bevy::diagnostic::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::core::prelude::*;
+use crate::game::GhostPassedTunnel;
+
+/// Records every gameplay message and every change of the game state or the state of a ghost
+/// into a JSON lines file, one object per line, so games can be analyzed and replayed as a timeline by other tools.
+///
+/// Every line has the frame ("tick"), the elapsed game time in seconds, the current level and the event,
+/// named like the message it was recorded from (like "DotWasEaten"), with the positions of the involved actors as tiles.
+pub struct EventLogPlugin {
+    /// The file the events are written to. An existing file is overwritten.
+    pub path: String,
+}
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        // the event log is just an extra, so the game is still played without it
+        let file = match File::create(&self.path) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!("could not create the event log {}, no events are recorded: {e}", self.path);
+                return;
+            }
+        };
+
+        app
+            .insert_resource(EventLog(BufWriter::new(file)))
+            .init_resource::<LastGhostStates>()
+            .add_systems(CleanUpGame, clear_last_ghost_states)
+            .add_systems(
+                Last,
+                (
+                    log_game_state_transitions,
+                    log_ghost_state_changes.run_if(in_game),
+                    log_edibles_eaten,
+                    log_pacman_hits,
+                    log_high_scores,
+                    log_ghosts_passing_tunnels,
+                    flush_event_log,
+                ).chain()
+            )
+        ;
+    }
+}
+
+/// The file the events get written to
+#[derive(Resource)]
+struct EventLog(BufWriter<File>);
+
+/// The last known state of every ghost, to only log states which actually changed
+#[derive(Resource, Default, Deref, DerefMut)]
+struct LastGhostStates(HashMap<Entity, GhostState>);
+
+/// A tile in the maze, as [x, y]
+type Tile = [isize; 2];
+
+/// One line of the event log
+#[derive(Serialize)]
+struct LogEntry {
+    tick: u32,
+    secs: f32,
+    level: usize,
+    #[serde(flatten)]
+    event: GameplayEvent,
+}
+
+/// Every event which gets logged, named after the message it is recorded from. Pacman is None if he is
+/// not in the maze at this moment.
+#[derive(Serialize)]
+#[serde(tag = "event")]
+enum GameplayEvent {
+    DotWasEaten {
+        pacman: Option<Tile>,
+    },
+    EnergizerWasEaten {
+        pacman: Option<Tile>,
+    },
+    GhostWasEaten {
+        ghost: Option<Ghost>,
+        position: Tile,
+    },
+    FruitWasEaten {
+        fruit: Fruit,
+        position: Tile,
+    },
+    PacmanWasHit {
+        ghost: Ghost,
+        pacman: Option<Tile>,
+    },
+    HighScoreWasBeaten {
+        score: usize,
+    },
+    GhostPassedTunnel {
+        ghost: Option<Ghost>,
+        position: Option<Tile>,
+    },
+    /// The states are written like "Game(Running)"
+    GameStateChanged {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    GhostStateChanged {
+        ghost: Ghost,
+        slot: usize,
+        /// None if the ghost was just spawned
+        from: Option<GhostState>,
+        to: GhostState,
+        position: Tile,
+    },
+}
+
+/// Writes events into the log, with the tick, time and level of the current frame.
+#[derive(SystemParam)]
+struct EventLogger<'w> {
+    log: ResMut<'w, EventLog>,
+    frame_count: Res<'w, FrameCount>,
+    time: Res<'w, Time>,
+    level: Res<'w, Level>,
+}
+
+impl EventLogger<'_> {
+    fn log(&mut self, event: GameplayEvent) {
+        let entry = LogEntry {
+            tick: self.frame_count.0,
+            secs: self.time.elapsed_secs(),
+            level: **self.level,
+            event,
+        };
+        let line = serde_json::to_string(&entry).expect("the event should be serializable");
+
+        if let Err(e) = writeln!(self.log.0, "{line}") {
+            warn!("could not write to the event log: {e}");
+        }
+    }
+}
+
+fn tile(transform: &Transform) -> Tile {
+    let pos = Pos::from_vec3(transform.translation);
+    [pos.x(), pos.y()]
+}
+
+fn log_game_state_transitions(
+    mut logger: EventLogger,
+    mut transition_reader: MessageReader<StateTransitionEvent<GameState>>,
+) {
+    for transition in transition_reader.read() {
+        logger.log(GameplayEvent::GameStateChanged {
+            from: transition.exited.map(|state| format!("{state:?}")),
+            to: transition.entered.map(|state| format!("{state:?}")),
+        });
+    }
+}
+
+/// Log every ghost whose state is different from the last frame. The state of a ghost is sometimes set
+/// without actually changing, so the last known state of every ghost is kept.
+fn log_ghost_state_changes(
+    mut logger: EventLogger,
+    mut last_states: ResMut<LastGhostStates>,
+    query: Query<(Entity, &Ghost, &GhostSlot, &GhostState, &Transform), Changed<GhostState>>,
+) {
+    for (entity, ghost, slot, state, transform) in &query {
+        let from = last_states.insert(entity, *state);
+
+        if from == Some(*state) {
+            continue;
+        }
+
+        logger.log(GameplayEvent::GhostStateChanged {
+            ghost: *ghost,
+            slot: **slot,
+            from,
+            to: *state,
+            position: tile(transform),
+        });
+    }
+}
+
+/// The ghosts get despawned when the game is cleaned up, so their states are not needed anymore.
+fn clear_last_ghost_states(
+    mut last_states: ResMut<LastGhostStates>,
+) {
+    last_states.clear();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_edibles_eaten(
+    mut logger: EventLogger,
+    mut dot_reader: MessageReader<DotWasEaten>,
+    mut energizer_reader: MessageReader<EnergizerWasEaten>,
+    mut ghost_reader: MessageReader<GhostWasEaten>,
+    mut fruit_reader: MessageReader<FruitWasEaten>,
+    pacman_query: Query<&Transform, With<Pacman>>,
+    ghost_query: Query<&Ghost>,
+) {
+    let pacman = pacman_query.single().ok().map(tile);
+
+    for _ in dot_reader.read() {
+        logger.log(GameplayEvent::DotWasEaten { pacman });
+    }
+
+    for _ in energizer_reader.read() {
+        logger.log(GameplayEvent::EnergizerWasEaten { pacman });
+    }
+
+    for GhostWasEaten(entity, transform) in ghost_reader.read() {
+        logger.log(GameplayEvent::GhostWasEaten {
+            ghost: ghost_query.get(*entity).ok().copied(),
+            position: tile(transform),
+        });
+    }
+
+    for FruitWasEaten(fruit, transform) in fruit_reader.read() {
+        logger.log(GameplayEvent::FruitWasEaten {
+            fruit: *fruit,
+            position: tile(transform),
+        });
+    }
+}
+
+fn log_pacman_hits(
+    mut logger: EventLogger,
+    mut hit_reader: MessageReader<PacmanWasHit>,
+    pacman_query: Query<&Transform, With<Pacman>>,
+) {
+    for PacmanWasHit(ghost) in hit_reader.read() {
+        logger.log(GameplayEvent::PacmanWasHit {
+            ghost: *ghost,
+            pacman: pacman_query.single().ok().map(tile),
+        });
+    }
+}
+
+fn log_high_scores(
+    mut logger: EventLogger,
+    mut high_score_reader: MessageReader<HighScoreWasBeaten>,
+    score: Res<Score>,
+) {
+    for _ in high_score_reader.read() {
+        logger.log(GameplayEvent::HighScoreWasBeaten { score: **score });
+    }
+}
+
+fn log_ghosts_passing_tunnels(
+    mut logger: EventLogger,
+    mut tunnel_reader: MessageReader<GhostPassedTunnel>,
+    ghost_query: Query<(&Ghost, &Transform)>,
+) {
+    for passed in tunnel_reader.read() {
+        let ghost = ghost_query.get(**passed).ok();
+
+        logger.log(GameplayEvent::GhostPassedTunnel {
+            ghost: ghost.map(|(ghost, _)| *ghost),
+            position: ghost.map(|(_, transform)| tile(transform)),
+        });
+    }
+}
+
+fn flush_event_log(
+    mut log: ResMut<EventLog>,
+) {
+    if let Err(e) = log.0.flush() {
+        warn!("could not write to the event log: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::prelude::*;
+    use crate::event_log::{GameplayEvent, LogEntry};
+
+    fn to_json(event: GameplayEvent) -> serde_json::Value {
+        let entry = LogEntry {
+            tick: 42,
+            secs: 1.5,
+            level: 3,
+            event,
+        };
+        serde_json::to_value(entry).unwrap()
+    }
+
+    #[test]
+    fn an_entry_has_the_event_name_next_to_its_fields() {
+        let json = to_json(GameplayEvent::DotWasEaten { pacman: Some([13, 7]) });
+
+        assert_eq!(json, json!({
+            "tick": 42,
+            "secs": 1.5,
+            "level": 3,
+            "event": "DotWasEaten",
+            "pacman": [13, 7],
+        }));
+    }
+
+    #[test]
+    fn actors_and_states_are_written_by_name() {
+        let json = to_json(GameplayEvent::GhostStateChanged {
+            ghost: Blinky,
+            slot: 0,
+            from: None,
+            to: GhostState::Scatter,
+            position: [13, 19],
+        });
+
+        assert_eq!(json, json!({
+            "tick": 42,
+            "secs": 1.5,
+            "level": 3,
+            "event": "GhostStateChanged",
+            "ghost": "Blinky",
+            "slot": 0,
+            "from": null,
+            "to": "Scatter",
+            "position": [13, 19],
+        }));
+    }
+
+    #[test]
+    fn missing_actors_are_written_as_null() {
+        let json = to_json(GameplayEvent::FruitWasEaten { fruit: Fruit::Cherry, position: [1, 2] });
+        assert_eq!(json["fruit"], json!("Cherry"));
+
+        let json = to_json(GameplayEvent::GhostPassedTunnel { ghost: None, position: None });
+        assert_eq!(json["event"], json!("GhostPassedTunnel"));
+        assert_eq!(json["ghost"], json!(null));
+        assert_eq!(json["position"], json!(null));
+    }
+
+    #[test]
+    fn game_states_are_written_like_their_debug_output() {
+        let json = to_json(GameplayEvent::GameStateChanged {
+            from: Some(format!("{:?}", Game(Ready))),
+            to: Some(format!("{:?}", Game(Running))),
+        });
+
+        assert_eq!(json["from"], json!("Game(Ready)"));
+        assert_eq!(json["to"], json!("Game(Running)"));
+    }
+}
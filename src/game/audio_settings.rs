@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use crate::core::prelude::*;
+
+pub(in crate::game) struct AudioSettingsPlugin;
+
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Startup,
+                load_audio_settings.run_if(resource_exists::<AudioSettingsPath>)
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_mute,
+                    save_audio_settings
+                        .run_if(resource_exists::<AudioSettingsPath>)
+                        .run_if(resource_changed::<AudioSettings>),
+                ).chain()
+            )
+        ;
+    }
+}
+
+/// Load the audio settings of the player. If there are none yet, the default settings are used.
+fn load_audio_settings(
+    path: Res<AudioSettingsPath>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    if let Some(settings) = AudioSettings::load(&**path) {
+        *audio_settings = settings;
+    }
+}
+
+/// Mute or unmute all sounds of the game by pressing M.
+fn toggle_mute(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        audio_settings.muted = !audio_settings.muted;
+    }
+}
+
+fn save_audio_settings(
+    path: Res<AudioSettingsPath>,
+    audio_settings: Res<AudioSettings>,
+) {
+    if let Err(e) = audio_settings.save(&**path) {
+        warn!("could not save the audio settings to {}: {e}", **path);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
 use crate::core::prelude::*;
 
 pub(super) struct CameraPlugin;
@@ -7,23 +8,29 @@ impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(OnEnter(Game(Start)), spawn_camera)
-            .add_systems(OnExit(Game(GameOver)), despawn_camera)
+            .add_systems(CleanUpGame, despawn_camera)
+            .add_systems(Update, (
+                toggle_fullscreen,
+                fit_camera_to_window,
+            ).chain())
         ;
     }
 }
 
+/// Spawn the camera centered on the maze and the UI above and beneath it.
 fn spawn_camera(
     mut commands: Commands,
     map_query: Query<&Map>,
 ) -> Result {
     let map = map_query.single()?;
+    let center = visible_area(map).center();
 
     commands.spawn((
         Name::new("GameCamera"),
         Camera2d,
-        Transform::from_translation(Vec3::new((map.width as f32 * FIELD_SIZE) / 2.0, (map.height as f32 * FIELD_SIZE) / 2.0, 1000.0)),
+        Transform::from_translation(center.extend(1000.0)),
     ));
-    
+
     Ok(())
 }
 
@@ -34,4 +41,79 @@ fn despawn_camera(
     for e in &query {
         commands.entity(e).despawn();
     }
-}
\ No newline at end of file
+}
+
+/// Switch between windowed and fullscreen mode by pressing F11.
+fn toggle_fullscreen(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F11) {
+        return;
+    }
+
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+
+    window.mode = match window.mode {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        _ => WindowMode::Windowed,
+    };
+}
+
+/// Zoom the camera so the maze and its UI fill as much of the window as possible, for every window size
+/// and aspect ratio. The UI gets scaled by the same factor, so one UI pixel always matches one world unit.
+///
+/// Does nothing without a window, like in headless games.
+fn fit_camera_to_window(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    map_query: Query<&Map>,
+    mut projection_query: Query<&mut Projection, With<Camera2d>>,
+    mut ui_scale: ResMut<UiScale>,
+    mut maze_viewport: ResMut<MazeViewport>,
+) {
+    let (Ok(window), Ok(map)) = (windows.single(), map_query.single()) else {
+        return;
+    };
+
+    let physical_size = window.physical_size().as_vec2();
+
+    if physical_size.min_element() <= 0.0 {
+        return;
+    }
+
+    let logical_per_world_unit = pixels_per_world_unit(physical_size, map) / window.scale_factor();
+
+    let scale = 1.0 / logical_per_world_unit;
+
+    for mut projection in &mut projection_query {
+        if matches!(&*projection, Projection::Orthographic(orthographic) if orthographic.scale == scale) {
+            continue;
+        }
+
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scale = scale;
+        }
+    }
+
+    if ui_scale.0 != logical_per_world_unit {
+        ui_scale.0 = logical_per_world_unit;
+    }
+
+    // The camera is centered on the visible area, so the area is centered on the window.
+    // UI coordinates start at the top left and grow downwards, world coordinates grow upwards.
+    let window_center = window.size() / logical_per_world_unit / 2.0;
+    let area_center = visible_area(map).center();
+    let bounds = map.bounds();
+    let viewport = MazeViewport {
+        maze: Rect::new(
+            window_center.x + bounds.min.x - area_center.x,
+            window_center.y - (bounds.max.y - area_center.y),
+            window_center.x + bounds.max.x - area_center.x,
+            window_center.y - (bounds.min.y - area_center.y),
+        ),
+    };
+
+    maze_viewport.set_if_neq(viewport);
+}
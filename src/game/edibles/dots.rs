@@ -1,4 +1,3 @@
-use std::time::Duration;
 use bevy::prelude::*;
 
 use crate::core::prelude::*;
@@ -29,7 +28,7 @@ impl Plugin for DotPlugin {
                 ),
             )
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 (
                     despawn_dots,
                     reset_eaten_dots
@@ -41,7 +40,7 @@ impl Plugin for DotPlugin {
 
 fn spawn_dots(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
     spawn_query: Query<&Tiles, With<DotSpawn>>,
 ) {
     let dots = commands.spawn((
@@ -58,10 +57,10 @@ fn spawn_dots(
                 Edible,
                 Name::new("Dot"),
                 Sprite {
-                    image: asset_server.load("textures/dot.png"),
                     custom_size: Some(Vec2::splat(DOT_DIMENSION)),
-                    ..default()
+                    ..theme_assets.sprite("dot")
                 },
+                ThemedImage("dot".to_string()),
                 Transform::from_translation(tiles.to_vec3(DOT_Z)),
             ));
         });
@@ -84,56 +83,14 @@ fn reset_eaten_dots(
 
 /// Play the famous waka waka when a dot was eaten.
 ///
-/// This code sucks, but I have no other way to do it. The problem is: If I would
-/// just play the waka every time a dot was eaten, the sound would overlap. I have no
-/// information if the sound finished playing, so I use a custom timer, which is set
-/// to the time of the track (0.3 seconds). Another waka can play when the timer finished.
-///
-/// But this leads to another problem: The waka makes a pause if another dot was eaten while
-/// the timer is still active. So I cache a waka if the dot was eaten while the timer is active.
-/// When the timer finishes and a waka is cached, it is instantly played and the timer gets reset.
-/// (This might lead to an additional waka playing, but more waka waka = more fun)
+/// A waka never overlaps itself. If dots are eaten while a waka plays, the next one
+/// starts right after it finished, so the waka doesn't make pauses.
 fn play_waka_when_dot_was_eaten(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut waka_timer: Local<Option<Timer>>,
-    mut cached: Local<bool>,
-    asset_server: Res<AssetServer>,
     mut message_reader: MessageReader<DotWasEaten>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
-    if let Some(ref mut timer) = *waka_timer {
-        timer.tick(time.delta());
-
-        if timer.is_finished() {
-            if *cached {
-                timer.reset();
-
-                commands.spawn((
-                    Name::new("WakaSound"),
-                    SoundEffect::new(1),
-                    AudioPlayer::<AudioSource>(asset_server.load("sounds/waka.ogg")),
-                ));
-
-                *cached = false;
-            } else {
-                *waka_timer = None
-            }
-        }
-    }
-
-    for _ in message_reader.read() {
-        match *waka_timer {
-            Some(_) => *cached = true,
-            None => {
-                *waka_timer = Some(Timer::new(Duration::from_secs_f32(0.3), TimerMode::Once));
-
-                commands.spawn((
-                    Name::new("WakaSound"),
-                    SoundEffect::new(1),
-                    AudioPlayer::<AudioSource>(asset_server.load("sounds/waka.ogg")),
-                ));
-            }
-        };
+    if message_reader.read().count() > 0 {
+        sound_writer.write(PlaySoundEffect(SoundKind::Waka));
     }
 }
 
@@ -33,7 +33,7 @@ impl Plugin for EnergizerPlugin {
                 despawn_energizer_timer,
             )
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 (
                     despawn_energizers,
                     despawn_energizer_timer
@@ -50,7 +50,7 @@ impl Plugin for EnergizerPlugin {
 
 fn spawn_energizer(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
     spawners: Query<&Tiles, With<EnergizerSpawn>>,
 ) {
     let energizers = commands.spawn((
@@ -67,10 +67,10 @@ fn spawn_energizer(
                 Edible,
                 Name::new("Energizer"),
                 Sprite {
-                    image: asset_server.load("textures/energizer.png"),
                     custom_size: Some(Vec2::splat(ENERGIZER_DIMENSION)),
-                    ..default()
+                    ..theme_assets.sprite("energizer")
                 },
+                ThemedImage("energizer".to_string()),
                 Transform::from_translation(tiles.to_vec3(ENERGIZER_Z)),
             ));
         });
@@ -12,6 +12,7 @@ impl Plugin for FruitPlugin {
             Update,
             (
                 spawn_fruit_when_dot_limit_reached.in_set(ProcessIntersectionsWithPacman),
+                move_wandering_fruits.in_set(MoveEntities),
                 update_despawn_timer,
                 despawn_fruit_if_timer_exceeded,
                 play_fruit_eaten_sound_when_fruit_was_eaten.in_set(ProcessIntersectionsWithPacman),
@@ -21,40 +22,120 @@ impl Plugin for FruitPlugin {
         )
         .add_systems(OnEnter(Game(PacmanHit)), despawn_fruit_and_timer)
         .add_systems(OnEnter(Game(LevelTransition)), despawn_fruit_and_timer)
-        .add_systems(OnExit(Game(GameOver)), despawn_fruit_and_timer);
+        .add_systems(CleanUpGame, despawn_fruit_and_timer);
     }
 }
 
-/// Spawn a fruit for the current level when a specific amount of dots
-/// was eaten.
+/// Spawn a fruit for the current level when the amount of eaten dots
+/// reaches one of the trigger values of the ruleset.
+#[allow(clippy::too_many_arguments)]
 fn spawn_fruit_when_dot_limit_reached(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
+    random: Res<Random>,
+    ruleset: Res<Ruleset>,
+    tile_graph: Res<TileGraph>,
     level: Res<Level>,
     eaten_dots: Res<EatenDots>,
-    specs_per_level: Res<SpecsPerLevel>,
     mut message_reader: MessageReader<DotWasEaten>,
     spawners: Query<&Tiles, With<FruitSpawn>>,
+    tunnels: Query<&Tiles, With<Tunnel>>,
+    ghost_houses: Query<&GhostHouseLayout>,
 ) {
     let num_eaten_dots = eaten_dots.get_eaten();
 
     for _ in message_reader.read() {
-        if let 70 | 170 = num_eaten_dots {
-            for tiles in &spawners {
-                let fruit = specs_per_level.get_for(&level).fruit_to_spawn;
-                commands.spawn((
-                    Name::new("Fruit"),
-                    fruit,
-                    Edible,
-                    Sprite {
-                        image: get_texture_for_fruit(&fruit, &asset_server),
-                        custom_size: Some(Vec2::splat(FRUIT_DIMENSION)),
-                        ..default()
-                    },
-                    Transform::from_translation(tiles.to_vec3(FRUIT_Z)),
-                ));
+        if ruleset.fruit.spawns_fruit_at(num_eaten_dots) {
+            let fruit = ruleset.fruit.fruit_to_spawn(&level, &random);
+            let sprite_name = get_sprite_name_for_fruit(&fruit);
+
+            match ruleset.fruit.mode {
+                FruitMode::Static => {
+                    for tiles in &spawners {
+                        commands.spawn((
+                            Name::new("Fruit"),
+                            fruit,
+                            Edible,
+                            Sprite {
+                                custom_size: Some(Vec2::splat(FRUIT_DIMENSION)),
+                                ..theme_assets.sprite(sprite_name)
+                            },
+                            ThemedImage(sprite_name.to_string()),
+                            Transform::from_translation(tiles.to_vec3(FRUIT_Z)),
+                        ));
+                    }
+                    commands.insert_resource(FruitDespawnTimer::new(ruleset.fruit.lifetime(&random)));
+                }
+                FruitMode::Wandering => {
+                    if let Some(path) = create_wandering_path(&random, &tile_graph, &tunnels, &ghost_houses) {
+                        let wandering_fruit = WanderingFruit::new(path);
+
+                        commands.spawn((
+                            Name::new("Fruit"),
+                            fruit,
+                            Edible,
+                            Speed(PACMAN_BASE_SPEED * 0.4),
+                            Sprite {
+                                custom_size: Some(Vec2::splat(FRUIT_DIMENSION)),
+                                ..theme_assets.sprite(sprite_name)
+                            },
+                            ThemedImage(sprite_name.to_string()),
+                            Transform::from_translation(wandering_fruit.displayed_coordinates()),
+                            wandering_fruit,
+                        ));
+                    }
+                }
             }
-            commands.insert_resource(FruitDespawnTimer::new());
+        }
+    }
+}
+
+/// Create the path of a wandering fruit: it enters through a random tunnel, walks to the
+/// ghost house entrance and leaves through another random tunnel (if there is one).
+///
+/// Returns None if the map has no tunnels or the ghost house cannot be reached.
+fn create_wandering_path(
+    random: &Random,
+    tile_graph: &TileGraph,
+    tunnels: &Query<&Tiles, With<Tunnel>>,
+    ghost_houses: &Query<&GhostHouseLayout>,
+) -> Option<Vec<Pos>> {
+    let tunnel_positions = tunnels.iter().map(|tiles| tiles.to_pos()).collect::<Vec<_>>();
+
+    if tunnel_positions.is_empty() {
+        return None;
+    }
+
+    let entry = tunnel_positions[random.zero_to(tunnel_positions.len())];
+    let exit_candidates = tunnel_positions
+        .iter()
+        .copied()
+        .filter(|pos| *pos != entry)
+        .collect::<Vec<_>>();
+    let exit = match exit_candidates.len() {
+        0 => entry,
+        len => exit_candidates[random.zero_to(len)],
+    };
+    let house_entrance = ghost_houses.iter().next()?.entrance_positions[0];
+
+    let mut path = tile_graph.shortest_path(entry, house_entrance)?;
+    path.extend(tile_graph.shortest_path(house_entrance, exit)?.into_iter().skip(1));
+    Some(path)
+}
+
+/// Move wandering fruits along their path and despawn them when they left the maze.
+fn move_wandering_fruits(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &Speed, &mut WanderingFruit, &mut Transform)>,
+) {
+    for (entity, speed, mut fruit, mut transform) in &mut query {
+        fruit.advance(**speed * time.delta_secs());
+
+        if fruit.reached_end() {
+            commands.entity(entity).despawn();
+        } else {
+            transform.translation = fruit.displayed_coordinates();
         }
     }
 }
@@ -107,15 +188,10 @@ fn despawn_fruit_and_timer(
 }
 
 fn play_fruit_eaten_sound_when_fruit_was_eaten(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut message_reader: MessageReader<FruitWasEaten>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
     for _ in message_reader.read() {
-        commands.spawn((
-            Name::new("FruitEatenSound"),
-            SoundEffect::new(1),
-            AudioPlayer::<AudioSource>(asset_server.load("sounds/fruit_eaten.ogg")),
-        ));
+        sound_writer.write(PlaySoundEffect(SoundKind::FruitEaten));
     }
 }
@@ -16,6 +16,10 @@ impl Plugin for GameStateTransitionPlugin {
     }
 }
 
+/// How long pacman may take to die at most. Ends the dying state if the dying animation does not
+/// finish, for example because it is repeating in a theme or pacman is hidden.
+const MAX_DYING_SECS: f32 = 5.0;
+
 /// Tells when to switch to the next state in the state machine
 #[derive(Resource, Deref, DerefMut)]
 struct StateTimer(Timer);
@@ -37,6 +41,8 @@ fn update_state(
     edibles_eaten_messages: MessageReader<EAllEdiblesEaten>,
     ghost_eaten_messages: MessageReader<GhostWasEaten>,
     game_restarted_messages: MessageReader<GameWasRestarted>,
+    animation_finished_messages: MessageReader<AnimationFinished>,
+    pacman_query: Query<Entity, With<Pacman>>,
 ) {
     match current_state.get() {
         Game(Start) => switch_when_timer_finished(
@@ -66,11 +72,14 @@ fn update_state(
             1.0,
             Game(PacmanDying),
         ),
-        Game(PacmanDying) => switch_when_timer_finished(
+        Game(PacmanDying) => switch_when_pacman_animation_finished(
             &mut commands,
             &state_timer,
             &mut next_state,
-            1.5,
+            animation_finished_messages,
+            &pacman_query,
+            "dying",
+            MAX_DYING_SECS,
             Game(PacmanDead),
         ),
         Game(PacmanDead) => {
@@ -113,6 +122,35 @@ fn switch_when_timer_finished(
     }
 }
 
+/// Switch to the new state when the given animation of pacman is over, so the state lasts exactly as long as the animation.
+/// If the animation did not finish after the given maximum time, switch anyway.
+#[allow(clippy::too_many_arguments)]
+fn switch_when_pacman_animation_finished(
+    commands: &mut Commands,
+    state_timer: &Option<Res<StateTimer>>,
+    game_state: &mut NextState<GameState>,
+    mut animation_finished_messages: MessageReader<AnimationFinished>,
+    pacman_query: &Query<Entity, With<Pacman>>,
+    animation: &str,
+    max_time: f32,
+    new_state: GameState,
+) {
+    let finished = animation_finished_messages
+        .read()
+        .any(|msg| msg.animation == animation && pacman_query.contains(msg.entity));
+
+    match state_timer {
+        Some(timer) => {
+            if finished || timer.is_finished() {
+                commands.remove_resource::<StateTimer>();
+                game_state.set(new_state);
+            }
+        }
+        None if finished => game_state.set(new_state),
+        None => commands.insert_resource(StateTimer(Timer::from_seconds(max_time, TimerMode::Once))),
+    }
+}
+
 fn switch_to_ready_or_game_over(
     commands: &mut Commands,
     state_timer: &Option<Res<StateTimer>>,
@@ -28,8 +28,14 @@ impl Plugin for GhostHouseGatePlugin {
 fn create_gate(
     mut commands: Commands,
     level: Res<Level>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    spawn_query: Query<&GhostSpawn>,
 ) {
-    commands.insert_resource(GhostHouseGate::new(&level));
+    let slots = spawn_query
+        .iter()
+        .map(|spawn| (spawn.slot, ghost_behaviours.get(&spawn.ghost)))
+        .collect::<Vec<_>>();
+    commands.insert_resource(GhostHouseGate::new(&level, slots));
 }
 
 fn update_ghost_house_gate(
@@ -44,6 +44,10 @@ impl Plugin for GhostPlugin {
                 OnEnter(Game(LevelTransition)),
                 despawn_ghosts,
             )
+            .add_systems(
+                CleanUpGame,
+                despawn_ghosts,
+            )
             .add_systems(
                 OnEnter(Game(GhostEatenPause)),
                 set_currently_eaten_ghost_invisible,
@@ -110,15 +114,10 @@ fn set_currently_eaten_ghost_visible(
 }
 
 fn play_ghost_eaten_sound_when_ghost_was_eaten(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut message_reader: MessageReader<GhostWasEaten>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
     if message_reader.read().count() > 0 {
-        commands.spawn((
-            Name::new("GhostEatenSound"),
-            SoundEffect::new(1),
-            AudioPlayer::<AudioSource>(asset_server.load("sounds/ghost_eaten.ogg")),
-        ));
+        sound_writer.write(PlaySoundEffect(SoundKind::GhostEaten));
     }
 }
\ No newline at end of file
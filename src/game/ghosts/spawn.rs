@@ -1,22 +1,21 @@
 use crate::core::prelude::*;
 use crate::game::ghosts::textures::create_animations_for_ghost;
-use crate::sprite_sheet::SpriteSheets;
 use bevy::prelude::*;
 
 pub fn spawn_ghosts(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    sprite_sheets: Res<SpriteSheets>,
+    theme_assets: ThemeAssets,
     level: Res<Level>,
     specs_per_level: Res<SpecsPerLevel>,
+    ghost_behaviours: Res<GhostBehaviours>,
     spawn_query: Query<&GhostSpawn>,
 ) {
     for spawn in &spawn_query {
         spawn_ghost(
             &mut commands,
             spawn,
-            &asset_server,
-            &sprite_sheets,
+            ghost_behaviours.get(&spawn.ghost),
+            &theme_assets,
             &level,
             &specs_per_level,
         );
@@ -26,14 +25,15 @@ pub fn spawn_ghosts(
 fn spawn_ghost(
     commands: &mut Commands,
     spawn: &GhostSpawn,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    behaviour: &GhostBehaviour,
+    theme_assets: &ThemeAssets,
     level: &Level,
     specs_per_level: &SpecsPerLevel,
 ) {
     let spawn_direction = spawn.spawn_direction;
     let spawn_coordinates = spawn.coordinates;
-    let mut animations = create_animations_for_ghost(&spawn.ghost, asset_server, sprite_sheets);
+    let themed_animations = create_animations_for_ghost(behaviour);
+    let mut animations = theme_assets.animations(&themed_animations);
     animations.change_animation_to(match spawn.spawn_direction {
         Up => "normal_up",
         Down => "normal_down",
@@ -45,17 +45,18 @@ fn spawn_ghost(
     commands.spawn((
         Name::new("Ghost"),
         spawn.ghost,
+        spawn.slot,
         spawn_direction,
         Speed(GHOST_BASE_SPEED * specs_per_level.get_for(level).ghost_normal_speed_modifier),
         Target::new(),
         Spawned,
         Sprite {
-            image: animations.current().texture(),
             custom_size: Some(Vec2::splat(GHOST_DIMENSION)),
-            ..default()
+            ..animations.current().sprite()
         },
         Transform::from_translation(spawn_coordinates),
         Visibility::Visible,
         animations,
+        themed_animations,
     ));
 }
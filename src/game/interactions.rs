@@ -26,16 +26,16 @@ fn pacman_hits_ghost(
     mut killed_message_writer: MessageWriter<PacmanWasHit>,
     mut eat_message_writer: MessageWriter<GhostWasEaten>,
     pacman_query: Query<&Transform, With<Pacman>>,
-    ghost_query: Query<(Entity, &Transform, &GhostState), With<Ghost>>,
+    ghost_query: Query<(Entity, &Ghost, &Transform, &GhostState)>,
 ) {
     for pacman_transform in &pacman_query {
-        for (entity, ghost_transform, state) in &ghost_query {
+        for (entity, ghost, ghost_transform, state) in &ghost_query {
             let pacman_pos = Pos::from_vec3(pacman_transform.translation);
             let ghost_pos = Pos::from_vec3(ghost_transform.translation);
 
             if pacman_pos == ghost_pos {
                 if let Scatter | Chase = state {
-                    killed_message_writer.write(PacmanWasHit);
+                    killed_message_writer.write(PacmanWasHit(*ghost));
                 }
 
                 if let Frightened = state {
@@ -6,17 +6,22 @@ pub(in crate::game) struct LevelPlugin;
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app
-            .insert_resource(Level(1))
             .add_systems(
                 OnExit(Game(LevelTransition)),
                 increase_level
             )
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 reset_level
             )
         ;
     }
+
+    /// The starting level is usually inserted after the plugins were added, so the level is set when the app is finished.
+    fn finish(&self, app: &mut App) {
+        let starting_level = **app.world().resource::<StartingLevel>();
+        app.insert_resource(Level(starting_level));
+    }
 }
 
 fn increase_level(
@@ -26,7 +31,8 @@ fn increase_level(
 }
 
 fn reset_level(
-    mut level: ResMut<Level>
+    mut level: ResMut<Level>,
+    starting_level: Res<StartingLevel>,
 ) {
-    level.0 = 1;
-}
\ No newline at end of file
+    level.0 = **starting_level;
+}
@@ -16,7 +16,7 @@ impl Plugin for LivesPlugin {
                 )
                     .run_if(in_state(Game(Running))))
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 reset_lives,
             )
         ;
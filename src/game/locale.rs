@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use crate::core::prelude::*;
+
+pub(in crate::game) struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                update_localized_texts.run_if(resource_exists::<Locale>)
+            )
+        ;
+    }
+}
+
+/// Show new localized texts in the active language, and every localized text again when the language changed.
+fn update_localized_texts(
+    locale: Res<Locale>,
+    locale_fonts: LocaleFonts,
+    mut query: Query<(Ref<LocalizedText>, &mut Text, &mut TextFont)>,
+) {
+    for (localized_text, mut text, mut text_font) in &mut query {
+        if !locale.is_changed() && !localized_text.is_added() {
+            continue;
+        }
+
+        let translation = locale.text(&localized_text);
+        text_font.font = locale_fonts.font_for(translation);
+        **text = translation.to_string();
+    }
+}
@@ -4,8 +4,10 @@ use crate::game::ghost_house_gate::GhostHouseGatePlugin;
 use crate::game::ghosts::GhostPlugin;
 use crate::game::interactions::InteractionsPlugin;
 use crate::game::level::LevelPlugin;
+use crate::game::locale::LocalePlugin;
 use crate::game::lives::LivesPlugin;
 use crate::game::animate_walls::AnimateWallsPlugin;
+use crate::game::audio_settings::AudioSettingsPlugin;
 use crate::game::pacman::PacmanPlugin;
 use crate::core::random::RandomPlugin;
 use crate::game::camera::CameraPlugin;
@@ -13,6 +15,7 @@ use crate::game::game_state_transition::GameStateTransitionPlugin;
 use crate::game::move_through_tunnel::MoveThroughTunnelPlugin;
 use crate::game::music::MusicPlugin;
 use crate::game::restart_game::RestartGamePlugin;
+use crate::game::save_game::SaveGamePlugin;
 use crate::game::schedule::SchedulePlugin;
 use crate::game::score::ScorePlugin;
 use crate::game::sound_effect::SoundEffectPlugin;
@@ -20,6 +23,8 @@ use crate::game::specs_per_level::SpecsPerLevelPlugin;
 use crate::game::speed::SpeedPlugin;
 use crate::game::state::StatePlugin;
 use crate::game::target::TargetPlugin;
+use crate::game::theme::ThemePlugin;
+use crate::game::tile_graph::TileGraphPlugin;
 use crate::game::ui::UIPlugin;
 
 pub mod interactions;
@@ -36,14 +41,21 @@ pub mod ghosts;
 mod schedule;
 pub mod state;
 pub mod target;
+mod theme;
+mod locale;
 mod move_through_tunnel;
 pub mod game_state_transition;
 pub mod sound_effect;
+mod audio_settings;
 pub mod music;
 pub mod camera;
 mod restart_game;
+mod save_game;
+mod tile_graph;
 mod ui;
 
+pub use move_through_tunnel::GhostPassedTunnel;
+
 /// Contains the entire gameplay logic for pacman.
 pub struct GamePlugin;
 
@@ -72,8 +84,13 @@ impl Plugin for GamePlugin {
                 MoveThroughTunnelPlugin,
                 GameStateTransitionPlugin,
                 SoundEffectPlugin,
+                AudioSettingsPlugin,
                 MusicPlugin,
                 RestartGamePlugin,
+                TileGraphPlugin,
+                ThemePlugin,
+                LocalePlugin,
+                SaveGamePlugin,
                 UIPlugin
             ))
         ;
@@ -1,3 +1,4 @@
+use crate::assets;
 use crate::core::prelude::*;
 use bevy::audio::{AudioSink, Volume};
 use bevy::prelude::*;
@@ -17,46 +18,51 @@ impl Plugin for MusicPlugin {
             .add_systems(OnEnter(Game(Running)), unmute_background_music)
             .add_systems(
                 Update,
-                (update_background_music, play_track).run_if(in_game),
+                (update_background_music, mix_tracks).chain().run_if(in_game),
             )
             .add_systems(OnExit(Game(Running)), mute_background_music)
-            .add_systems(OnExit(Game(GameOver)), despawn_tracks);
+            .add_systems(CleanUpGame, despawn_tracks);
     }
 }
 
 fn play_start_sound(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
-    commands.spawn((
-        Name::new("StartSound"),
-        SoundEffect::new(5),
-        AudioPlayer::<AudioSource>(asset_server.load("sounds/start.ogg")),
-    ));
+    sound_writer.write(PlaySoundEffect(SoundKind::Start));
 }
 
 /// Starts every background track at the same time with volume of 0.
+///
+/// Every siren stage gets its own track, even if stages share the same sound, so the
+/// mixer can fade between them.
 fn init_background_music(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    ruleset: Res<Ruleset>,
 ) {
-    start_background_track(
-        &mut commands,
-        &asset_server,
-        SirenBackground,
-        "sounds/siren.ogg",
-    );
+    for (i, stage) in ruleset.siren.stages.iter().enumerate() {
+        start_background_track(
+            &mut commands,
+            &asset_server,
+            SirenBackground(i),
+            stage.track.clone(),
+            stage.speed,
+        );
+    }
+
     start_background_track(
         &mut commands,
         &asset_server,
         FrightenedBackground,
-        "sounds/frightened.ogg",
+        assets::sounds::FRIGHTENED_OGG,
+        1.0,
     );
     start_background_track(
         &mut commands,
         &asset_server,
         EatenBackground,
-        "sounds/eaten.ogg",
+        assets::sounds::EATEN_OGG,
+        1.0,
     );
 }
 
@@ -64,14 +70,19 @@ fn start_background_track(
     commands: &mut Commands,
     asset_server: &AssetServer,
     marker: impl Component,
-    path: &'static str,
+    path: impl Into<String>,
+    speed: f32,
 ) {
+    let path = path.into();
+
     commands.spawn((
-        Name::new(path),
+        Name::new(path.clone()),
         BackgroundTrack,
         marker,
         AudioPlayer::<AudioSource>(asset_server.load(path)),
-        PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+        PlaybackSettings::LOOP
+            .with_volume(Volume::Linear(0.0))
+            .with_speed(speed),
     ));
 }
 
@@ -85,156 +96,82 @@ fn mute_background_music(mut background_music: ResMut<BackgroundMusic>) {
     background_music.muted = true
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_background_music(
     mut background_music: ResMut<BackgroundMusic>,
     energizer_timer_opt: Option<Res<EnergizerTimer>>,
     eaten_dots: Res<EatenDots>,
-    query: Query<&GhostState, With<Ghost>>,
+    level: Res<Level>,
+    specs_per_level: Res<SpecsPerLevel>,
+    ruleset: Res<Ruleset>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    query: Query<(&Ghost, &GhostState)>,
 ) {
-    let eaten_ghosts = query.iter().filter(|s| s == &&Eaten).count();
+    let eaten_ghosts = query.iter().filter(|(_, s)| s == &&Eaten).count();
 
-    if eaten_ghosts > 0 {
-        background_music.current_track = EatenTrack
+    let current_track = if eaten_ghosts > 0 {
+        EatenTrack
     } else if energizer_timer_opt.is_some() {
-        background_music.current_track = FrightenedTrack
+        FrightenedTrack
     } else {
-        background_music.current_track =
-            match eaten_dots.get_eaten() as f32 / eaten_dots.get_max() as f32 {
-                r if (0.0..0.25).contains(&r) => Siren1,
-                r if (0.25..0.5).contains(&r) => Siren2,
-                r if (0.5..0.75).contains(&r) => Siren3,
-                _ => Siren4,
-            }
-    }
+        let eaten_dots_share = eaten_dots.get_eaten() as f32 / eaten_dots.get_max() as f32;
+        let elroy_in_maze = query
+            .iter()
+            .any(|(ghost, _)| ghost_behaviours.get(ghost).speed_profile == SpeedProfile::Elroy);
+        let elroy_phase = if elroy_in_maze {
+            specs_per_level.get_for(&level).elroy_phase(eaten_dots.get_remaining())
+        } else {
+            0
+        };
+
+        Siren(ruleset.siren.stage_for(eaten_dots_share, elroy_phase))
+    };
+
+    background_music.current_track = current_track;
 }
 
+/// Fade the current track in and every other track out. The track that plays gets the volume
+/// of the music channel. When the music is muted, every track gets silenced instantly.
 #[allow(clippy::type_complexity)]
-fn play_track(
+fn mix_tracks(
+    time: Res<Time>,
+    ruleset: Res<Ruleset>,
     background_music: Res<BackgroundMusic>,
-    mut siren_tracks: Query<
-        &mut AudioSink,
-        (
-            With<SirenBackground>,
-            Without<FrightenedBackground>,
-            Without<EatenBackground>,
-        ),
-    >,
-    mut frightened_tracks: Query<
-        &mut AudioSink,
-        (
-            With<FrightenedBackground>,
-            Without<SirenBackground>,
-            Without<EatenBackground>,
-        ),
-    >,
-    mut eaten_tracks: Query<
-        &mut AudioSink,
+    audio_settings: Res<AudioSettings>,
+    mut query: Query<
         (
-            With<EatenBackground>,
-            Without<SirenBackground>,
-            Without<FrightenedBackground>,
+            &mut AudioSink,
+            Option<&SirenBackground>,
+            Has<FrightenedBackground>,
+            Has<EatenBackground>,
         ),
+        With<BackgroundTrack>,
     >,
-) -> Result {
-    if !background_music.is_changed() {
-        return Ok(());
-    }
-
-    let mut siren_track = if let Ok(t) = siren_tracks.single_mut() {
-        t
-    } else {
-        return Ok(());
-    };
-    let mut frightened_track = if let Ok(t) = frightened_tracks.single_mut() {
-        t
-    } else {
-        return Ok(());
-    };
-    let mut eaten_track = if let Ok(t) = eaten_tracks.single_mut() {
-        t
+) {
+    let volume = audio_settings.effective_volume(AudioChannel::Music);
+    let max_step = if ruleset.siren.crossfade_secs > 0.0 {
+        time.delta_secs() / ruleset.siren.crossfade_secs
     } else {
-        return Ok(());
+        f32::MAX
     };
-    let mixer = Mixer::new(&mut siren_track, &mut frightened_track, &mut eaten_track);
-
-    if background_music.muted {
-        mixer.mute_all();
-        return Ok(());
-    }
-
-    match &background_music.current_track {
-        Siren1 => mixer.play_siren_1(),
-        Siren2 => mixer.play_siren_2(),
-        Siren3 => mixer.play_siren_3(),
-        Siren4 => mixer.play_siren_4(),
-        FrightenedTrack => mixer.play_frightened(),
-        EatenTrack => mixer.play_eaten(),
-    }
-
-    Ok(())
-}
 
-struct Mixer<'a> {
-    siren_track: &'a mut AudioSink,
-    frightened_track: &'a mut AudioSink,
-    eaten_track: &'a mut AudioSink,
-}
-
-impl<'a> Mixer<'a> {
-    pub fn new(
-        siren_track: &'a mut AudioSink,
-        frightened_track: &'a mut AudioSink,
-        eaten_track: &'a mut AudioSink,
-    ) -> Self {
-        Self {
-            siren_track,
-            frightened_track,
-            eaten_track,
+    for (mut sink, siren, frightened, eaten) in &mut query {
+        if background_music.muted {
+            sink.set_volume(Volume::Linear(0.0));
+            continue;
         }
-    }
 
-    fn play_siren_1(self) {
-        self.play_siren(1.0)
-    }
-
-    fn play_siren_2(self) {
-        self.play_siren(1.05)
-    }
+        let is_current = match background_music.current_track {
+            Siren(stage) => siren.is_some_and(|s| **s == stage),
+            FrightenedTrack => frightened,
+            EatenTrack => eaten,
+        };
+        let target = if is_current { volume } else { 0.0 };
+        let current = sink.volume().to_linear();
 
-    fn play_siren_3(self) {
-        self.play_siren(1.1)
-    }
-
-    fn play_siren_4(self) {
-        self.play_siren(1.15)
-    }
-
-    fn play_siren(
-        self,
-        speed: f32,
-    ) {
-        self.siren_track.set_volume(Volume::Linear(1.0));
-        self.siren_track.set_speed(speed);
-        self.frightened_track.set_volume(Volume::Linear(0.0));
-        self.eaten_track.set_volume(Volume::Linear(0.0));
-    }
-
-    fn play_frightened(self) {
-        self.siren_track.set_volume(Volume::Linear(0.0));
-        self.frightened_track.set_volume(Volume::Linear(1.0));
-        self.eaten_track.set_volume(Volume::Linear(0.0));
-    }
-
-    fn play_eaten(self) {
-        self.siren_track.set_volume(Volume::Linear(0.0));
-        self.frightened_track.set_volume(Volume::Linear(0.0));
-        self.eaten_track.set_volume(Volume::Linear(1.0));
-    }
-
-    fn mute_all(self) {
-        self.siren_track.set_volume(Volume::Linear(0.0));
-        self.frightened_track.set_volume(Volume::Linear(0.0));
-        self.eaten_track.set_volume(Volume::Linear(0.0));
+        if current != target {
+            sink.set_volume(Volume::Linear(current + (target - current).clamp(-max_step, max_step)));
+        }
     }
 }
 
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+/// Ghosts nearer than this amount of steps are avoided.
+const DANGER_RADIUS: usize = 6;
+/// Frightened ghosts nearer than this amount of steps are chased.
+const CHASE_RADIUS: usize = 10;
+/// An energizer must be active at least this amount of seconds, else frightened ghosts are not chased
+/// and avoided instead, as they might turn back soon.
+const MIN_FRIGHTENED_SECONDS: f32 = 2.0;
+/// Added to every direction except the current one, so the bot does not jitter between equally good tiles.
+const TURN_PENALTY: f32 = 0.5;
+
+/// Let the bot decide where pacman should move next.
+///
+/// The bot picks goals (frightened ghosts to hunt, fruits, dots and, if in danger, energizers) and moves
+/// to the neighbour tile with the lowest cost. The cost is the distance to the nearest goal plus a penalty
+/// for every ghost nearby, weighted by how dangerous the ghost currently is.
+#[allow(clippy::too_many_arguments)]
+pub(in crate::game) fn set_wished_direction_from_bot(
+    tile_graph: Res<TileGraph>,
+    energizer_timer: Option<Res<EnergizerTimer>>,
+    mut pacman_query: Query<(&Transform, &Dir, &PacmanController, &mut WishedDirection), With<Pacman>>,
+    ghost_query: Query<(&Transform, &GhostState), With<Ghost>>,
+    dot_query: Query<&Transform, With<Dot>>,
+    energizer_query: Query<&Transform, With<Energizer>>,
+    fruit_query: Query<&Transform, With<Fruit>>,
+) {
+    // the distance fields are expensive, so they are only created if there is a bot to use them
+    if !pacman_query.iter().any(|(_, _, controller, _)| *controller == PacmanController::Bot) {
+        return;
+    }
+
+    let frightened_seconds = energizer_timer.map(|timer| timer.remaining()).unwrap_or_default();
+    let ghosts = ghost_query
+        .iter()
+        .map(|(transform, state)| (Pos::from_vec3(transform.translation), *state))
+        .collect::<Vec<_>>();
+    let danger_fields = ghosts
+        .iter()
+        .filter_map(|(pos, state)| {
+            let weight = danger_weight(state, frightened_seconds);
+            (weight > 0.0).then(|| (weight, tile_graph.distance_field([*pos])))
+        })
+        .collect::<Vec<_>>();
+
+    for (transform, direction, controller, mut wished_direction) in &mut pacman_query {
+        if *controller != PacmanController::Bot {
+            continue;
+        }
+
+        let position = Pos::from_vec3(transform.translation);
+        let pacman_field = tile_graph.distance_field([position]);
+        let in_danger = danger_fields
+            .iter()
+            .any(|(_, field)| field.get(&position).is_some_and(|d| *d < DANGER_RADIUS));
+
+        let mut goals = Vec::new();
+
+        if frightened_seconds >= MIN_FRIGHTENED_SECONDS {
+            goals.extend(
+                ghosts
+                    .iter()
+                    .filter(|(pos, state)| *state == Frightened && pacman_field.get(pos).is_some_and(|d| *d < CHASE_RADIUS))
+                    .map(|(pos, _)| *pos),
+            );
+        }
+
+        if goals.is_empty() {
+            goals.extend(fruit_query.iter().map(|tf| Pos::from_vec3(tf.translation)));
+            goals.extend(dot_query.iter().map(|tf| Pos::from_vec3(tf.translation)));
+
+            if in_danger || goals.is_empty() {
+                goals.extend(energizer_query.iter().map(|tf| Pos::from_vec3(tf.translation)));
+            }
+        }
+
+        let goal_field = tile_graph.distance_field(goals);
+
+        **wished_direction = tile_graph
+            .neighbours(position)
+            .into_iter()
+            .map(|(neighbour, dir)| {
+                let turn_penalty = if dir == *direction { 0.0 } else { TURN_PENALTY };
+                (dir, goal_cost(&goal_field, &neighbour) + danger_cost(&danger_fields, &neighbour) + turn_penalty)
+            })
+            .min_by(|(_, cost_a), (_, cost_b)| cost_a.total_cmp(cost_b))
+            .map(|(dir, _)| dir);
+    }
+}
+
+/// Return how much a ghost in the given state should be avoided.
+fn danger_weight(
+    state: &GhostState,
+    frightened_seconds: f32,
+) -> f32 {
+    match state {
+        Chase => 1.0,
+        Scatter => 0.8,
+        Spawned => 0.5,
+        Frightened if frightened_seconds < MIN_FRIGHTENED_SECONDS => 0.5,
+        Frightened | Eaten => 0.0,
+    }
+}
+
+fn goal_cost(
+    goal_field: &HashMap<Pos, usize>,
+    pos: &Pos,
+) -> f32 {
+    goal_field
+        .get(pos)
+        .map(|distance| *distance as f32)
+        .unwrap_or(goal_field.len() as f32)
+}
+
+fn danger_cost(
+    danger_fields: &[(f32, HashMap<Pos, usize>)],
+    pos: &Pos,
+) -> f32 {
+    danger_fields
+        .iter()
+        .filter_map(|(weight, field)| field.get(pos).map(|distance| (weight, *distance)))
+        .filter(|(_, distance)| *distance < DANGER_RADIUS)
+        .map(|(weight, distance)| weight * ((DANGER_RADIUS - distance).pow(2) * 10) as f32)
+        .sum()
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use crate::core::prelude::*;
+
+/// How far the left stick of a gamepad must be pushed before it counts as input.
+const STICK_DEAD_ZONE: f32 = 0.5;
+
+pub(in crate::game) fn set_wished_direction_from_keyboard(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut query: Query<(&PacmanController, &mut WishedDirection), With<Pacman>>,
+) {
+    for (controller, mut wished_direction) in &mut query {
+        if *controller == PacmanController::Keyboard {
+            **wished_direction = get_direction_from_keyboard(&keyboard_input);
+        }
+    }
+}
+
+fn get_direction_from_keyboard(keyboard_input: &ButtonInput<KeyCode>) -> Option<Dir> {
+    if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
+        return Some(Left);
+    }
+
+    if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
+        return Some(Right);
+    }
+
+    if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
+        return Some(Up);
+    }
+
+    if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
+        return Some(Down);
+    }
+
+    None
+}
+
+pub(in crate::game) fn set_wished_direction_from_gamepad(
+    gamepads: Query<&Gamepad>,
+    mut query: Query<(&PacmanController, &mut WishedDirection), With<Pacman>>,
+) {
+    let direction = gamepads.iter().find_map(get_direction_from_gamepad);
+
+    for (controller, mut wished_direction) in &mut query {
+        if *controller == PacmanController::Gamepad {
+            **wished_direction = direction;
+        }
+    }
+}
+
+fn get_direction_from_gamepad(gamepad: &Gamepad) -> Option<Dir> {
+    if gamepad.pressed(GamepadButton::DPadLeft) {
+        return Some(Left);
+    }
+
+    if gamepad.pressed(GamepadButton::DPadRight) {
+        return Some(Right);
+    }
+
+    if gamepad.pressed(GamepadButton::DPadUp) {
+        return Some(Up);
+    }
+
+    if gamepad.pressed(GamepadButton::DPadDown) {
+        return Some(Down);
+    }
+
+    let stick = gamepad.left_stick();
+
+    if stick.length() < STICK_DEAD_ZONE {
+        None
+    } else if stick.x.abs() > stick.y.abs() {
+        Some(if stick.x < 0.0 { Left } else { Right })
+    } else {
+        Some(if stick.y < 0.0 { Down } else { Up })
+    }
+}
+
+pub(in crate::game) fn set_wished_direction_from_replay(
+    replay_inputs: Option<ResMut<ReplayInputs>>,
+    mut query: Query<(&PacmanController, &mut WishedDirection), With<Pacman>>,
+) {
+    let Some(mut replay_inputs) = replay_inputs else {
+        return;
+    };
+
+    for (controller, mut wished_direction) in &mut query {
+        if *controller == PacmanController::Replay {
+            **wished_direction = replay_inputs.next_input();
+        }
+    }
+}
+
+/// When all inputs of the replay were played, the player takes over with the keyboard.
+pub(in crate::game) fn switch_to_keyboard_when_replay_finished(
+    replay_inputs: Option<Res<ReplayInputs>>,
+    mut query: Query<&mut PacmanController, With<Pacman>>,
+) {
+    if replay_inputs.is_some_and(|inputs| !inputs.is_finished()) {
+        return;
+    }
+
+    for mut controller in &mut query {
+        if *controller == PacmanController::Replay {
+            *controller = PacmanController::Keyboard;
+        }
+    }
+}
+
+/// Record the wished direction of every frame, no matter which controller is used.
+pub(in crate::game) fn record_wished_directions(
+    mut recorded_inputs: ResMut<RecordedInputs>,
+    query: Query<&WishedDirection, With<Pacman>>,
+) {
+    for wished_direction in &query {
+        recorded_inputs.push(**wished_direction);
+    }
+}
+
+/// Start a new recording when the game gets restarted.
+pub(in crate::game) fn clear_recorded_inputs(
+    mut recorded_inputs: ResMut<RecordedInputs>,
+) {
+    recorded_inputs.clear();
+}
+
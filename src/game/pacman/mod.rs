@@ -1,11 +1,17 @@
 use bevy::prelude::*;
+use bevy::time::common_conditions::paused;
+use crate::game::pacman::bot::set_wished_direction_from_bot;
 use crate::game::pacman::edible_eaten::EdibleEatenPlugin;
-use crate::game::pacman::movement::{InputBuffer, move_pacman, reset_input_buffer, set_direction_based_on_keyboard_input};
+use crate::game::pacman::controller::{clear_recorded_inputs, record_wished_directions, set_wished_direction_from_gamepad, set_wished_direction_from_keyboard, set_wished_direction_from_replay, switch_to_keyboard_when_replay_finished};
+use crate::game::pacman::movement::{move_pacman, reset_input_buffer, steer_pacman};
 use crate::game::pacman::spawn::spawn_pacman;
 use crate::game::pacman::textures::{start_pacman_animation, update_pacman_appearance};
+use crate::game::ui::options_screen::options_screen_closed;
 
 use crate::core::prelude::*;
 
+mod bot;
+mod controller;
 mod movement;
 mod spawn;
 pub(crate) mod textures;
@@ -17,13 +23,24 @@ impl Plugin for PacmanPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(EdibleEatenPlugin)
-            .insert_resource(InputBuffer(None))
             .add_systems(OnEnter(Game(Ready)), spawn_pacman)
             .add_systems(OnEnter(Game(Running)), start_pacman_animation)
             .add_systems(Update, (
                 move_pacman,
-                set_direction_based_on_keyboard_input,
-                update_pacman_appearance.after(set_direction_based_on_keyboard_input)
+                (
+                    set_wished_direction_from_keyboard,
+                    set_wished_direction_from_gamepad,
+                    // the replay was recorded without the frames the game was paused
+                    set_wished_direction_from_replay.run_if(not(paused)),
+                    set_wished_direction_from_bot,
+                ).before(steer_pacman).run_if(options_screen_closed),
+                steer_pacman,
+                switch_to_keyboard_when_replay_finished.after(steer_pacman),
+                record_wished_directions
+                    .after(steer_pacman)
+                    .run_if(not(paused))
+                    .run_if(resource_exists::<RecordedInputs>),
+                update_pacman_appearance.after(steer_pacman)
             ).run_if(in_state(Game(Running))))
             .add_systems(OnEnter(Game(PacmanHit)), (
                 stop_animation,
@@ -39,6 +56,10 @@ impl Plugin for PacmanPlugin {
                 reset_input_buffer
             ))
             .add_systems(OnExit(Game(LevelTransition)), despawn_pacman)
+            .add_systems(CleanUpGame, (
+                despawn_pacman,
+                clear_recorded_inputs.run_if(resource_exists::<RecordedInputs>)
+            ))
             .add_systems(OnEnter(Game(GhostEatenPause)), set_invisible)
             .add_systems(OnExit(Game(GhostEatenPause)), set_visible)
         ;
@@ -62,15 +83,11 @@ fn play_the_dying_animation(
     }
 }
 
+/// The dying sound has the highest priority, so it silences every other sound effect.
 fn play_the_dying_sound(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
-    commands.spawn((
-        Name::new("PacmanDyingSound"),
-        SoundEffect::new(4),
-        AudioPlayer::<AudioSource>(asset_server.load("sounds/dying.ogg")),
-    ));
+    sound_writer.write(PlaySoundEffect(SoundKind::Dying));
 }
 
 fn despawn_pacman(
@@ -55,17 +55,18 @@ fn get_modifiers_for_direction(direction: &Dir) -> (f32, f32) {
     }
 }
 
-pub(in crate::game) fn set_direction_based_on_keyboard_input(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut input_buffer: ResMut<InputBuffer>,
-    mut pacman_query: Query<(&Transform, &mut Dir), With<Pacman>>,
+/// Take the wished direction of every pacman, regardless of which controller it came from.
+///
+/// If the direction points into a wall or pacman is not centered enough to turn, the direction
+/// gets buffered and tried again in the next frames.
+pub(in crate::game) fn steer_pacman(
+    mut pacman_query: Query<(&Transform, &mut Dir, &WishedDirection, &mut InputBuffer), With<Pacman>>,
     wall_query: Query<&Transform, With<Wall>>
 ) {
-    for (transform, mut direction) in &mut pacman_query {
+    for (transform, mut direction, wished_direction, mut input_buffer) in &mut pacman_query {
         let position = Pos::from_vec3(transform.translation);
-        let wished_direction = get_wished_direction(&keyboard_input, &input_buffer);
 
-        if let Some(dir) = wished_direction {
+        if let Some(dir) = wished_direction.or(**input_buffer) {
             let position_center = position.to_vec3(PACMAN_Z);
             let position_in_direction = position.neighbour_in_direction(dir);
             let position_in_direction_is_wall = wall_query.iter().any(|transform| Pos::from_vec3(transform.translation) == position_in_direction);
@@ -80,31 +81,12 @@ pub(in crate::game) fn set_direction_based_on_keyboard_input(
     }
 }
 
-/// Return the direction pacman should move to next. If no matching keyboard key was pressed, return the last buffered input.
-fn get_wished_direction(keyboard_input: &ButtonInput<KeyCode>, input_buffer: &InputBuffer) -> Option<Dir> {
-    if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-        return Some(Left);
-    }
-
-    if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-        return Some(Right);
-    }
-
-    if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
-        return Some(Up);
-    }
-
-    if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
-        return Some(Down);
-    }
-
-    **input_buffer
-}
-
 pub (in crate::game) fn reset_input_buffer(
-    mut input_buffer: ResMut<InputBuffer>
+    mut query: Query<&mut InputBuffer, With<Pacman>>
 ) {
-    input_buffer.0 = None;
+    for mut input_buffer in &mut query {
+        input_buffer.0 = None;
+    }
 }
 
 /// Return if pacman is near enough to his currents position center to move to an orthogonal position.
@@ -119,6 +101,3 @@ fn is_centered_enough(coordinates: Vec3, direction: Dir, position_coordinates: V
     }
 }
 
-/// Saves the wished direction pacman should move to next.
-#[derive(Deref, DerefMut, Resource)]
-pub struct InputBuffer(pub Option<Dir>);
\ No newline at end of file
@@ -1,30 +1,32 @@
 use crate::core::prelude::*;
 use crate::game::pacman::textures::create_pacman_animations;
-use crate::sprite_sheet::SpriteSheets;
 use bevy::prelude::*;
 
 pub(crate) fn spawn_pacman(
     mut commands: Commands,
-    sprite_sheets: Res<SpriteSheets>,
+    theme_assets: ThemeAssets,
     level: Res<Level>,
     specs_per_level: Res<SpecsPerLevel>,
+    selected_controller: Res<SelectedController>,
     spawns: Query<&Tiles, With<PacmanSpawn>>,
 ) -> Result {
     let tiles = spawns.single()?;
     let transform = Transform::from_translation(tiles.to_vec3(PACMAN_Z));
-    let mut animations = create_pacman_animations(&sprite_sheets);
+    let themed_animations = create_pacman_animations();
+    let mut animations = theme_assets.animations(&themed_animations);
     animations.stop();
 
     commands.spawn((
         Pacman,
+        **selected_controller,
         Speed(PACMAN_BASE_SPEED * specs_per_level.get_for(&level).pacman_normal_speed_modifier),
         Sprite {
-            image: animations.current().texture(),
             custom_size: Some(Vec2::splat(PACMAN_DIMENSION)),
-            ..default()
+            ..animations.current().sprite()
         },
         transform,
         animations,
+        themed_animations,
     ));
 
     Ok(())
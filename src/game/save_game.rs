@@ -0,0 +1,235 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+pub(in crate::game) struct SaveGamePlugin;
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Last,
+                save_game_when_quit
+                    .run_if(resource_exists::<SaveGamePath>)
+                    .run_if(in_game)
+            )
+            .add_systems(
+                OnEnter(Game(GameOver)),
+                delete_saved_game.run_if(resource_exists::<SaveGamePath>)
+            )
+            .add_systems(
+                Update,
+                start_saved_game
+                    .run_if(resource_exists::<SaveGamePath>)
+                    .run_if(in_game)
+            )
+            .add_systems(
+                OnEnter(SpawnMaze(EnhanceMap)),
+                restore_progress.run_if(resource_exists::<ResumedGame>)
+            )
+            .add_systems(
+                Update,
+                (
+                    restore_edibles,
+                    restore_ghost_house_and_schedule,
+                    restore_actors,
+                    finish_resume
+                )
+                    .chain()
+                    .run_if(resource_exists::<ResumedGame>)
+                    .run_if(in_state(Game(Ready)))
+            )
+        ;
+    }
+}
+
+/// The saved game which gets restored right now
+#[derive(Resource, Deref)]
+struct ResumedGame(SaveGame);
+
+/// Save the game in progress when the game is quit. Only games which are ready or running are saved, as the other
+/// states (like pacman dying or a finished level) cannot be resumed in the middle.
+#[allow(clippy::too_many_arguments)]
+fn save_game_when_quit(
+    mut exit_reader: MessageReader<AppExit>,
+    path: Res<SaveGamePath>,
+    game_state: Res<State<GameState>>,
+    score: Res<Score>,
+    high_score: Res<HighScore>,
+    lives: Res<Lives>,
+    level: Res<Level>,
+    points_required_for_extra_life: Res<PointsRequiredForExtraLife>,
+    ghost_schedule: Option<Res<GhostSchedule>>,
+    ghost_house_gate: Option<Res<GhostHouseGate>>,
+    dot_query: Query<&Transform, With<Dot>>,
+    energizer_query: Query<&Transform, With<Energizer>>,
+    pacman_query: Query<(&Transform, &Dir), With<Pacman>>,
+    ghost_query: Query<(&GhostSlot, &Transform, &Dir, &GhostState), With<Ghost>>,
+) {
+    if exit_reader.read().count() == 0 || !matches!(game_state.get(), Game(Ready) | Game(Running)) {
+        return;
+    }
+
+    let (Some(ghost_schedule), Some(ghost_house_gate)) = (ghost_schedule, ghost_house_gate) else {
+        return;
+    };
+
+    let save_game = SaveGame {
+        score: **score,
+        high_score: high_score.score,
+        high_score_was_beaten: high_score.was_beaten,
+        lives: **lives,
+        level: **level,
+        points_required_for_extra_life: **points_required_for_extra_life,
+        dots: dot_query.iter().map(tile).collect(),
+        energizers: energizer_query.iter().map(tile).collect(),
+        ghost_schedule: ghost_schedule.save(),
+        ghost_house_gate: ghost_house_gate.save(),
+        pacman: pacman_query.single().ok().map(|(transform, direction)| SavedPacman {
+            x: transform.translation.x,
+            y: transform.translation.y,
+            direction: *direction,
+        }),
+        ghosts: ghost_query
+            .iter()
+            .map(|(slot, transform, direction, state)| SavedGhost {
+                slot: **slot,
+                x: transform.translation.x,
+                y: transform.translation.y,
+                direction: *direction,
+                state: *state,
+            })
+            .collect(),
+    };
+
+    match save_game.save(&**path) {
+        Ok(_) => info!("saved the game to {}", **path),
+        Err(e) => warn!("could not save the game to {}: {e}", **path),
+    }
+}
+
+/// The tile of an edible, as saved in the [SaveGame]
+fn tile(transform: &Transform) -> (isize, isize) {
+    let pos = Pos::from_vec3(transform.translation);
+    (pos.x(), pos.y())
+}
+
+fn delete_saved_game(
+    path: Res<SaveGamePath>,
+) {
+    let _ = std::fs::remove_file(&**path);
+}
+
+/// When the player wants to continue the saved game, rebuild the maze and start the saved game on it.
+fn start_saved_game(
+    mut commands: Commands,
+    mut resume_reader: MessageReader<ResumeSavedGame>,
+    path: Res<SaveGamePath>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if resume_reader.read().count() == 0 {
+        return;
+    }
+
+    match SaveGame::load(&**path) {
+        Some(save_game) => {
+            commands.insert_resource(ResumedGame(save_game));
+            next_state.set(SpawnMaze(SpawnMapScene));
+        }
+        None => warn!("there is no saved game in {}", **path),
+    }
+}
+
+/// Restore the score, lives and level before the game starts, as the ghost house gate, the speeds
+/// and more are created for the current level.
+fn restore_progress(
+    resumed_game: Res<ResumedGame>,
+    mut score: ResMut<Score>,
+    mut high_score: ResMut<HighScore>,
+    mut lives: ResMut<Lives>,
+    mut level: ResMut<Level>,
+    mut points_required_for_extra_life: ResMut<PointsRequiredForExtraLife>,
+) {
+    **score = resumed_game.score;
+    high_score.score = resumed_game.high_score;
+    high_score.was_beaten = resumed_game.high_score_was_beaten;
+    **lives = resumed_game.lives;
+    **level = resumed_game.level;
+    **points_required_for_extra_life = resumed_game.points_required_for_extra_life;
+}
+
+/// Despawn every dot and energizer which was already eaten in the saved game.
+fn restore_edibles(
+    mut commands: Commands,
+    resumed_game: Res<ResumedGame>,
+    mut eaten_dots: ResMut<EatenDots>,
+    dot_query: Query<(Entity, &Transform), With<Dot>>,
+    energizer_query: Query<(Entity, &Transform), With<Energizer>>,
+) {
+    for (entity, transform) in &dot_query {
+        if !resumed_game.dots.contains(&tile(transform)) {
+            commands.entity(entity).despawn();
+            eaten_dots.increment();
+        }
+    }
+
+    for (entity, transform) in &energizer_query {
+        if !resumed_game.energizers.contains(&tile(transform)) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn restore_ghost_house_and_schedule(
+    resumed_game: Res<ResumedGame>,
+    mut ghost_schedule: ResMut<GhostSchedule>,
+    mut ghost_house_gate: ResMut<GhostHouseGate>,
+) {
+    ghost_schedule.restore(&resumed_game.ghost_schedule);
+    ghost_house_gate.restore(&resumed_game.ghost_house_gate);
+}
+
+/// Put pacman and the ghosts back where they were.
+///
+/// Ghosts which were in the ghost house or on their way back stay at their spawn and leave the house again.
+/// Frightened ghosts get the state of the schedule, as the energizer is not saved.
+fn restore_actors(
+    resumed_game: Res<ResumedGame>,
+    ghost_schedule: Res<GhostSchedule>,
+    mut pacman_query: Query<(&mut Transform, &mut Dir), (With<Pacman>, Without<Ghost>)>,
+    mut ghost_query: Query<(&GhostSlot, &mut Transform, &mut Dir, &mut GhostState, &mut Target), With<Ghost>>,
+) {
+    if let Some(saved) = resumed_game.pacman {
+        for (mut transform, mut direction) in &mut pacman_query {
+            transform.translation.x = saved.x;
+            transform.translation.y = saved.y;
+            *direction = saved.direction;
+        }
+    }
+
+    for (slot, mut transform, mut direction, mut state, mut target) in &mut ghost_query {
+        let Some(saved) = resumed_game.ghosts.iter().find(|ghost| ghost.slot == **slot) else {
+            continue;
+        };
+
+        if !matches!(saved.state, Scatter | Chase | Frightened) {
+            continue;
+        }
+
+        transform.translation.x = saved.x;
+        transform.translation.y = saved.y;
+        *direction = saved.direction;
+        *state = ghost_schedule.current_state();
+        target.clear();
+    }
+}
+
+/// The saved game is deleted once it was resumed, so it can only be continued once.
+fn finish_resume(
+    mut commands: Commands,
+    path: Res<SaveGamePath>,
+) {
+    commands.remove_resource::<ResumedGame>();
+    let _ = std::fs::remove_file(&**path);
+}
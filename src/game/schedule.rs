@@ -16,19 +16,23 @@ impl Plugin for SchedulePlugin {
     }
 }
 
+/// Register the schedule of the level the game starts with. This is the first level, unless a saved game is resumed.
 fn register_start_schedule(
     mut commands: Commands,
+    level: Res<Level>,
     schedule_by_level: Res<ScheduleByLevel>,
 ) {
-    commands.insert_resource(schedule_by_level.get_schedule_for_level(&Level(1)));
+    commands.insert_resource(schedule_by_level.get_schedule_for_level(&level));
 }
 
+/// Switch to the schedule of the new level. A restored schedule already belongs to the current
+/// level, so the level itself is compared instead of checking if it changed.
 fn switch_schedule_when_level_changed(
     mut schedule: ResMut<GhostSchedule>,
     level: Res<Level>,
     schedule_by_level: Res<ScheduleByLevel>,
 ) {
-    if !level.is_changed() { return; }
+    if schedule.is_for_level(&level) { return; }
 
     *schedule = schedule_by_level.get_schedule_for_level(&level);
 }
@@ -37,8 +37,9 @@ impl Plugin for ScorePlugin {
                 ),
             )
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 (
+                    despawn_score_texts,
                     reset_score,
                     reset_high_score
                 )
@@ -105,22 +106,14 @@ fn reset_ghost_eaten_counter(
 fn add_points_for_eaten_fruit_and_display_score_text(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    ruleset: Res<Ruleset>,
     mut score: ResMut<Score>,
     mut message_reader: MessageReader<FruitWasEaten>,
 ) {
     for event in message_reader.read() {
         let (fruit, transform) = (event.0, event.1);
 
-        let points = match fruit {
-            Cherry => 100,
-            Strawberry => 300,
-            Peach => 500,
-            Apple => 700,
-            Grapes => 1000,
-            Galaxian => 2000,
-            Bell => 3000,
-            Key => 5000
-        };
+        let points = ruleset.fruit.points_for(&fruit);
 
         let mut coordinates = transform.translation;
         coordinates.z = TEXT_Z;
@@ -190,16 +183,11 @@ fn update_high_score(
 }
 
 fn play_highscore_broken_sound(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut message_reader: MessageReader<HighScoreWasBeaten>,
+    mut sound_writer: MessageWriter<PlaySoundEffect>,
 ) {
     for _ in message_reader.read() {
-        commands.spawn((
-            Name::new("HighScoreBrokenSound"),
-            SoundEffect::new(3),
-            AudioPlayer::<AudioSource>(asset_server.load("sounds/high_score.ogg")),
-        ));
+        sound_writer.write(PlaySoundEffect(SoundKind::HighScore));
     }
 }
 
@@ -1,3 +1,4 @@
+use bevy::audio::Volume;
 use bevy::prelude::*;
 use crate::core::prelude::*;
 
@@ -8,28 +9,80 @@ impl Plugin for SoundEffectPlugin {
         app
             .add_systems(
                 Update,
-                update_sound_effects
+                (
+                    play_sound_effects,
+                    update_sound_effect_volumes.run_if(resource_changed::<AudioSettings>),
+                )
             )
         ;
     }
 }
 
-/// Updates the timer on a all sound effects. As I currently know no other way to check if a sound
-/// finished playing, this is the solution.
-/// It doesn't matter if the sound plays longer than its timer, the entity can be deleted
-/// anyway without interrupting it.
-fn update_sound_effects(
+/// Play the requested sound effects, with respect to their concurrency limits and priorities.
+///
+/// Every sound effect despawns itself when it finished playing, so the playing sounds are just
+/// the existing sound effect entities. Sounds that should wait until the current one of their
+/// kind finished are kept until the next frames.
+fn play_sound_effects(
     mut commands: Commands,
-    time: Res<Time>,
-    mut sounds: Query<(Entity, &mut SoundEffect)>
+    asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
+    mut message_reader: MessageReader<PlaySoundEffect>,
+    mut waiting: Local<Vec<SoundKind>>,
+    sounds: Query<(Entity, &SoundEffect)>,
 ) {
-    let delta = time.delta();
+    let mut playing = sounds
+        .iter()
+        .map(|(entity, sound)| (entity, **sound))
+        .collect::<Vec<_>>();
+    let requests = waiting
+        .drain(..)
+        .chain(message_reader.read().map(|message| **message))
+        .collect::<Vec<_>>();
 
-    for (entity, mut sound) in &mut sounds {
-        sound.update(delta);
+    let volume = Volume::Linear(audio_settings.effective_volume(AudioChannel::Effects));
 
-        if sound.finished() {
-            commands.entity(entity).despawn();
+    for kind in requests {
+        let highest_priority = playing.iter().map(|(_, k)| k.priority()).max().unwrap_or_default();
+
+        if kind.priority() < highest_priority {
+            continue;
+        }
+
+        if kind.priority() > highest_priority {
+            for (entity, _) in playing.drain(..) {
+                commands.entity(entity).despawn();
+            }
         }
+
+        if playing.iter().filter(|(_, k)| *k == kind).count() >= kind.max_concurrent() {
+            if kind.waits_when_busy() && !waiting.contains(&kind) {
+                waiting.push(kind);
+            }
+
+            continue;
+        }
+
+        let entity = commands
+            .spawn((
+                Name::new(format!("{kind:?}Sound")),
+                SoundEffect(kind),
+                AudioPlayer::<AudioSource>(asset_server.load(kind.path())),
+                PlaybackSettings::DESPAWN.with_volume(volume),
+            ))
+            .id();
+        playing.push((entity, kind));
     }
-}
\ No newline at end of file
+}
+
+/// Sound effects which are already playing follow changes of the effects volume or the mute.
+fn update_sound_effect_volumes(
+    audio_settings: Res<AudioSettings>,
+    mut query: Query<&mut AudioSink, With<SoundEffect>>,
+) {
+    let volume = Volume::Linear(audio_settings.effective_volume(AudioChannel::Effects));
+
+    for mut sink in &mut query {
+        sink.set_volume(volume);
+    }
+}
@@ -31,28 +31,29 @@ fn update_ghost_speed(
     level: Res<Level>,
     eaten_dots: Res<EatenDots>,
     specs_per_level: Res<SpecsPerLevel>,
+    ghost_behaviours: Res<GhostBehaviours>,
     mut ghost_query: Query<GhostSpeedUpdateComponents>,
     tunnel_query: Query<&Transform, Or<(With<Tunnel>, With<TunnelHallway>)>>,
 ) {
     for mut comps in ghost_query.iter_mut() {
-        match *comps.ghost {
-            Blinky => update_blinky_speed(
+        match ghost_behaviours.get(comps.ghost).speed_profile {
+            SpeedProfile::Elroy => update_elroy_speed(
                 &level,
                 &specs_per_level,
                 &eaten_dots,
                 &mut comps,
                 &tunnel_query,
             ),
-            _ => update_non_blinky_speed(&level, &specs_per_level, &mut comps, &tunnel_query),
+            SpeedProfile::Normal => update_normal_speed(&level, &specs_per_level, &mut comps, &tunnel_query),
         }
     }
 }
 
-/// Blinkys speed is set differently, as he has the elroy mode. He
-/// gets two speed bonuses, depending on the remaining dots on the board.
+/// Ghosts with the elroy profile (like Blinky) get their speed set differently. They
+/// get two speed bonuses, depending on the remaining dots on the board.
 /// The amount of dots to trigger elroy depends on the current level.
 #[allow(clippy::type_complexity)]
-fn update_blinky_speed(
+fn update_elroy_speed(
     level: &Level,
     specs_per_level: &SpecsPerLevel,
     eaten_dots: &EatenDots,
@@ -68,17 +69,18 @@ fn update_blinky_speed(
         *comps.speed = Speed(GHOST_BASE_SPEED * spec.ghost_tunnel_speed_modifier);
     } else if *comps.state == Frightened {
         *comps.speed = Speed(GHOST_BASE_SPEED * spec.ghost_frightened_speed_modifier)
-    } else if remaining_dots <= spec.elroy_2_dots_left {
-        *comps.speed = Speed(GHOST_BASE_SPEED * spec.elroy_2_speed_modifier)
-    } else if remaining_dots <= spec.elroy_1_dots_left {
-        *comps.speed = Speed(GHOST_BASE_SPEED * spec.elroy_1_speed_modifier)
     } else {
-        *comps.speed = Speed(GHOST_BASE_SPEED * spec.ghost_normal_speed_modifier)
+        let modifier = match spec.elroy_phase(remaining_dots) {
+            2 => spec.elroy_2_speed_modifier,
+            1 => spec.elroy_1_speed_modifier,
+            _ => spec.ghost_normal_speed_modifier,
+        };
+        *comps.speed = Speed(GHOST_BASE_SPEED * modifier)
     }
 }
 
 #[allow(clippy::type_complexity)]
-fn update_non_blinky_speed(
+fn update_normal_speed(
     level: &Level,
     specs_per_level: &SpecsPerLevel,
     comps: &mut GhostSpeedUpdateComponentsItem,
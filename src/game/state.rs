@@ -31,7 +31,7 @@ impl Plugin for StatePlugin {
 #[query_data(mutable)]
 struct StateUpdateComponents<'a> {
     entity: Entity,
-    ghost: &'a Ghost,
+    slot: &'a GhostSlot,
     state: &'a mut GhostState,
     target: &'a mut Target,
     direction: &'a mut Dir,
@@ -43,9 +43,11 @@ fn update_state(
     energizer_over_messages: MessageReader<EnergizerOver>,
     energizer_eaten_messages: MessageReader<EnergizerWasEaten>,
     ghost_eaten_messages: MessageReader<GhostWasEaten>,
+    ghost_house_query: Query<&GhostHouseLayout>,
     spawns_query: Query<&GhostSpawn>,
     mut query: Query<StateUpdateComponents, With<Ghost>>,
-) {
+) -> Result {
+    let ghost_house = ghost_house_query.single()?;
     let energizer_eaten = energizer_eaten(energizer_eaten_messages);
     let energizer_over = energizer_over(energizer_over_messages);
     let ghost_eaten_events = collect_events(ghost_eaten_messages);
@@ -62,26 +64,33 @@ fn update_state(
         }
 
         match *components.state {
-            Spawned => process_spawned(&schedule, &mut components, &spawns_query),
+            Spawned => process_spawned(&schedule, &mut components, ghost_house),
             Scatter | Chase => process_scatter_chase(&schedule, &mut components),
             Frightened => process_frightened(&schedule, energizer_over, &mut components),
             Eaten => process_eaten(&mut components, &spawns_query),
         }
     }
+
+    Ok(())
 }
 
 fn update_state_on_eaten_pause(
     schedule: Res<GhostSchedule>,
+    ghost_house_query: Query<&GhostHouseLayout>,
     spawns_query: Query<&GhostSpawn>,
     mut query: Query<StateUpdateComponents, With<Ghost>>,
-) {
+) -> Result {
+    let ghost_house = ghost_house_query.single()?;
+
     for mut components in &mut query {
         match *components.state {
-            Spawned => process_spawned(&schedule, &mut components, &spawns_query),
+            Spawned => process_spawned(&schedule, &mut components, ghost_house),
             Eaten => process_eaten(&mut components, &spawns_query),
             _ => continue,
         }
     }
+
+    Ok(())
 }
 
 fn collect_events<M: Copy + Message>(mut message_reader: MessageReader<M>) -> Vec<M> {
@@ -122,17 +131,12 @@ fn process_energizer_eaten(components: &mut StateUpdateComponentsItem) {
 fn process_spawned(
     schedule: &GhostSchedule,
     components: &mut StateUpdateComponentsItem,
-    spawns_query: &Query<&GhostSpawn>,
+    ghost_house: &GhostHouseLayout,
 ) {
-    let blinky_spawn = spawns_query
-        .iter()
-        .find(|spawn| spawn.ghost == Blinky)
-        .expect("blinky should have a spawn");
-
     let coordinates = components.transform.translation;
-    if coordinates.xy_equal(&blinky_spawn.coordinates) {
+    if coordinates.xy_equal(&ghost_house.entrance_coordinates) {
         *components.state = schedule.current_state();
-        *components.direction = blinky_spawn.spawn_direction;
+        *components.direction = ghost_house.entrance_direction;
     }
 }
 
@@ -179,14 +183,11 @@ fn process_eaten(
 ) {
     let respawn = spawns_query
         .iter()
-        .find(|spawn| match *components.ghost {
-            Blinky => spawn.ghost == Pinky,
-            _ => spawn.ghost == *components.ghost,
-        })
+        .find(|spawn| spawn.slot == *components.slot)
         .expect("every ghost should have a spawn");
     let coordinates = components.transform.translation;
 
-    if coordinates.xy_equal(&respawn.coordinates) {
+    if coordinates.xy_equal(&respawn.respawn_coordinates) {
         *components.state = Spawned
     }
 }
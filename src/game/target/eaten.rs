@@ -0,0 +1,117 @@
+use crate::core::prelude::*;
+use crate::game::target::TargetSetter;
+
+impl<'a, 'b, 'c, 'd> TargetSetter<'a, 'b, 'c, 'd> {
+    /// Determine the next target coordinates for a ghost when in "Eaten" state.
+    ///
+    /// When eaten, a ghost walks to the ghost house and enters it. When at the ghost house, he aligns perfectly
+    /// before the entrance, moves than to the house center and finally to his spawn coordinates, which depend on the ghost type.
+    pub fn set_eaten_target(&mut self) {
+        if self.is_directly_before_entrance() {
+            self.move_in_house_center()
+        } else if self.is_before_entrance() {
+            self.move_directly_before_entrance()
+        } else if self.is_in_center() {
+            self.move_to_respawn()
+        } else {
+            // TODO: Maybe only take this branch when not already in the ghost house, just to avoid bugs
+            self.move_to_nearest_position_before_entrance()
+        }
+    }
+
+    /// Return if the ghost is perfectly centered in front of the ghost house entrance.
+    fn is_directly_before_entrance(&self) -> bool {
+        self.components
+            .transform
+            .translation
+            .xy_equal(&self.ghost_house.entrance_coordinates)
+    }
+
+    fn move_in_house_center(&mut self) {
+        *self.components.direction = self.ghost_house.exit_direction.opposite();
+        self.components.target.set(self.ghost_house.center_coordinates);
+    }
+
+    /// Return if the ghost is just on a position in front of the house.
+    fn is_before_entrance(&self) -> bool {
+        self.ghost_house
+            .entrance_positions
+            .into_iter()
+            .any(|pos| pos == Pos::from_vec3(self.components.transform.translation))
+    }
+
+    fn move_directly_before_entrance(&mut self) {
+        let in_front_of_house = self.ghost_house.entrance_coordinates;
+        let position_coordinates =
+            Pos::from_vec3(self.components.transform.translation).to_vec3(0.0);
+
+        *self.components.direction = match self.ghost_house.exit_direction {
+            Up | Down => match in_front_of_house.x < position_coordinates.x {
+                true => Left,
+                false => Right,
+            },
+            Left | Right => match in_front_of_house.y < position_coordinates.y {
+                true => Down,
+                false => Up,
+            },
+        };
+        self.components.target.set(in_front_of_house);
+    }
+
+    fn is_in_center(&self) -> bool {
+        self.components
+            .transform
+            .translation
+            .xy_equal(&self.ghost_house.center_coordinates)
+    }
+
+    fn move_to_respawn(&mut self) {
+        let center = self.ghost_house.center_coordinates;
+        let respawn = self.get_spawn(*self.components.slot).respawn_coordinates;
+
+        *self.components.direction = match self.ghost_house.exit_direction {
+            Up | Down => match respawn.x < center.x {
+                true => Left,
+                false => Right,
+            },
+            Left | Right => match respawn.y < center.y {
+                true => Down,
+                false => Up,
+            },
+        };
+        self.components.target.set(respawn);
+    }
+
+    fn move_to_nearest_position_before_entrance(&mut self) {
+        match self.eaten_ghost_navigation {
+            EatenGhostNavigation::ShortestPath => self.follow_home_distance_field(),
+            EatenGhostNavigation::ArcadeGreedy => self.move_greedy_to_entrance(),
+        }
+    }
+
+    /// Take the step which is nearest to the entrance on the shortest path. If the ghost is somewhere
+    /// the distance field does not know, it moves greedy instead.
+    fn follow_home_distance_field(&mut self) {
+        let position = Pos::from_vec3(self.components.transform.translation);
+
+        match self.home_distance_field.next_step(self.tile_graph, position, *self.components.direction) {
+            Some(dir) => self.set_target_to_neighbour((position.neighbour_in_direction(dir), dir)),
+            None => self.move_greedy_to_entrance(),
+        }
+    }
+
+    fn move_greedy_to_entrance(&mut self) {
+        let position = Pos::from_vec3(self.components.transform.translation);
+        let nearest_spawn_position = self
+            .ghost_house
+            .entrance_positions
+            .into_iter()
+            .map(|pos| (pos, pos.distance(&position)))
+            .min_by(|(_, dis_a), (_, dis_b)| dis_a.partial_cmp(dis_b).unwrap())
+            .map(|(pos, _)| pos)
+            .unwrap();
+
+        let next_target_neighbour = self.get_nearest_neighbour_to(nearest_spawn_position);
+        self.set_target_to_neighbour(next_target_neighbour)
+    }
+}
@@ -0,0 +1,354 @@
+use std::cmp::Ordering;
+
+use bevy::ecs::query::QueryData;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+
+use crate::core::prelude::*;
+
+mod eaten;
+mod spawned;
+
+type Neighbour = (Pos, Dir);
+
+pub(in crate::game) struct TargetPlugin;
+
+impl Plugin for TargetPlugin {
+    fn build(
+        &self,
+        app: &mut App,
+    ) {
+        app.add_systems(
+            Update,
+            set_target.in_set(SetTarget).run_if(in_state(Game(Running))),
+        )
+        .add_systems(
+            Update,
+            set_target_on_ghost_pause
+                .in_set(SetTarget)
+                .run_if(in_state(Game(GhostEatenPause))),
+        );
+    }
+}
+
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct TargetComponents<'a> {
+    ghost: &'a Ghost,
+    slot: &'a GhostSlot,
+    target: &'a mut Target,
+    direction: &'a mut Dir,
+    transform: &'a Transform,
+    state: &'a GhostState,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set_target(
+    random: Res<Random>,
+    ghost_house_gate: Res<GhostHouseGate>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    ruleset: Res<Ruleset>,
+    tile_graph: Res<TileGraph>,
+    home_distance_field: Res<HomeDistanceField>,
+    corner_query: Query<(&GhostCorner, &Tiles)>,
+    wall_query: Query<&Transform, With<Wall>>,
+    ghost_house_query: Query<&GhostHouseLayout>,
+    ghost_spawn_query: Query<&GhostSpawn>,
+    pacman_query: Query<(&Transform, &Dir), With<Pacman>>,
+    one_ways: Query<&Tiles, With<OneWay>>,
+    mut ghost_query: Query<TargetComponents, Without<Pacman>>,
+) -> Result {
+    let (pm_transform, pm_dir) = pacman_query.single()?;
+    let ghost_house = *ghost_house_query.single()?;
+    let ghost_positions = get_ghost_positions(&ghost_query);
+
+    for mut components in &mut ghost_query {
+        if components.target.is_set() {
+            continue;
+        }
+
+        let state = *components.state;
+        let mut setter = TargetSetter::new(
+            &random,
+            &ghost_house_gate,
+            &ghost_behaviours,
+            ruleset.eaten_ghost_navigation,
+            &tile_graph,
+            &home_distance_field,
+            *pm_transform,
+            *pm_dir,
+            &ghost_positions,
+            &corner_query,
+            &wall_query,
+            ghost_house,
+            &ghost_spawn_query,
+            &one_ways,
+            &mut components,
+        );
+
+        match state {
+            Chase => setter.set_chase_target(),
+            Scatter => setter.set_scatter_target(),
+            Frightened => setter.set_frightened_target(),
+            Eaten => setter.set_eaten_target(),
+            Spawned => setter.set_spawned_target(),
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the target when on ghost pause (meaning only eaten and spawned)
+#[allow(clippy::too_many_arguments)]
+fn set_target_on_ghost_pause(
+    random: Res<Random>,
+    ghost_house_gate: Res<GhostHouseGate>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    ruleset: Res<Ruleset>,
+    tile_graph: Res<TileGraph>,
+    home_distance_field: Res<HomeDistanceField>,
+    corner_query: Query<(&GhostCorner, &Tiles)>,
+    wall_query: Query<&Transform, With<Wall>>,
+    ghost_house_query: Query<&GhostHouseLayout>,
+    ghost_spawn_query: Query<&GhostSpawn>,
+    pacman_query: Query<(&Transform, &Dir), With<Pacman>>,
+    one_ways: Query<&Tiles, With<OneWay>>,
+    mut ghost_query: Query<TargetComponents, Without<Pacman>>,
+) -> Result {
+    let (pm_transform, pm_dir) = pacman_query.single()?;
+    let ghost_house = *ghost_house_query.single()?;
+    let ghost_positions = get_ghost_positions(&ghost_query);
+
+    for mut components in &mut ghost_query {
+        if components.target.is_set() {
+            continue;
+        }
+
+        let state = *components.state;
+        let mut setter = TargetSetter::new(
+            &random,
+            &ghost_house_gate,
+            &ghost_behaviours,
+            ruleset.eaten_ghost_navigation,
+            &tile_graph,
+            &home_distance_field,
+            *pm_transform,
+            *pm_dir,
+            &ghost_positions,
+            &corner_query,
+            &wall_query,
+            ghost_house,
+            &ghost_spawn_query,
+            &one_ways,
+            &mut components,
+        );
+
+        match state {
+            Eaten => setter.set_eaten_target(),
+            Spawned => setter.set_spawned_target(),
+            _ => continue,
+        }
+    }
+
+    Ok(())
+}
+
+struct TargetSetter<'a, 'b, 'c, 'd> {
+    random: &'a Random,
+    ghost_house_gate: &'a GhostHouseGate,
+    ghost_behaviours: &'a GhostBehaviours,
+    eaten_ghost_navigation: EatenGhostNavigation,
+    tile_graph: &'a TileGraph,
+    home_distance_field: &'a HomeDistanceField,
+    pacman_transform: Transform,
+    pacman_direction: Dir,
+    ghost_positions: &'a [(Ghost, Pos)],
+    corner_positions: HashMap<Ghost, Pos>,
+    wall_positions: HashSet<Pos>,
+    ghost_house: GhostHouseLayout,
+    ghost_spawns: HashMap<GhostSlot, GhostSpawn>,
+    one_ways: HashSet<Pos>,
+    components: &'a mut TargetComponentsItem<'b, 'c, 'd>,
+}
+
+impl<'a, 'b, 'c, 'd> TargetSetter<'a, 'b, 'c, 'd> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        random: &'a Random,
+        ghost_house_gate: &'a GhostHouseGate,
+        ghost_behaviours: &'a GhostBehaviours,
+        eaten_ghost_navigation: EatenGhostNavigation,
+        tile_graph: &'a TileGraph,
+        home_distance_field: &'a HomeDistanceField,
+        pacman_transform: Transform,
+        pacman_direction: Dir,
+        ghost_positions: &'a [(Ghost, Pos)],
+        corner_query: &Query<(&GhostCorner, &Tiles)>,
+        wall_query: &Query<&Transform, With<Wall>>,
+        ghost_house: GhostHouseLayout,
+        ghost_spawn_query: &Query<&GhostSpawn>,
+        one_ways: &Query<&Tiles, With<OneWay>>,
+        components: &'a mut TargetComponentsItem<'b, 'c, 'd>,
+    ) -> Self {
+        let corner_positions = corner_query
+            .iter()
+            .map(|(corner, tiles)| (**corner, tiles.to_pos()))
+            .collect();
+        let wall_positions = wall_query
+            .iter()
+            .map(|transform| Pos::from_vec3(transform.translation))
+            .collect();
+        let ghost_spawns = ghost_spawn_query
+            .iter()
+            .map(|spawn| (spawn.slot, *spawn))
+            .collect();
+        let one_ways = one_ways.iter().map(|t| t.to_pos()).collect();
+
+        Self {
+            random,
+            ghost_spawns,
+            ghost_house_gate,
+            ghost_behaviours,
+            eaten_ghost_navigation,
+            tile_graph,
+            home_distance_field,
+            pacman_transform,
+            pacman_direction,
+            ghost_positions,
+            corner_positions,
+            wall_positions,
+            ghost_house,
+            one_ways,
+            components,
+        }
+    }
+
+    /// Let the registered behaviour of the ghost decide where to go.
+    fn set_chase_target(&mut self) {
+        let ghost = *self.components.ghost;
+        let context = ChaseContext {
+            ghost,
+            ghost_position: Pos::from_vec3(self.components.transform.translation),
+            pacman_position: Pos::from_vec3(self.pacman_transform.translation),
+            pacman_direction: self.pacman_direction,
+            scatter_position: self.scatter_position(),
+            ghost_positions: self.ghost_positions,
+        };
+        let target = (self.ghost_behaviours.get(&ghost).chase_target)(&context);
+        let next_target_neighbour = self.get_nearest_neighbour_to(target);
+        self.set_target_to_neighbour(next_target_neighbour)
+    }
+
+    fn set_scatter_target(&mut self) {
+        let corner_pos = self.scatter_position();
+        let next_target_neighbour = self.get_nearest_neighbour_to(corner_pos);
+        self.set_target_to_neighbour(next_target_neighbour)
+    }
+
+    fn scatter_position(&self) -> Pos {
+        let corner = self.ghost_behaviours.get(self.components.ghost).scatter_corner;
+        *self.corner_positions.get(&corner).expect("the map should have a corner for every ghost")
+    }
+
+    fn set_frightened_target(&mut self) {
+        let ghost_pos = Pos::from_vec3(self.components.transform.translation);
+        let opposite_dir = self.components.direction.opposite();
+
+        let possible_neighbours = ghost_pos
+            .neighbours_with_directions()
+            .into_iter()
+            .filter(|(_, dir)| *dir != opposite_dir)
+            .filter(|(pos, _)| !self.wall_positions.contains(pos))
+            .filter(|(_, dir)| {
+                if self.is_on_one_way(ghost_pos) {
+                    *dir == Left || *dir == Right
+                } else {
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+        let next_target_neighbour = match possible_neighbours.len() {
+            0 => (ghost_pos.neighbour_in_direction(opposite_dir), opposite_dir),
+            1 => *possible_neighbours.first().unwrap(),
+            len => *possible_neighbours.get(self.random.zero_to(len)).unwrap(),
+        };
+        self.set_target_to_neighbour(next_target_neighbour)
+    }
+
+    /// Get the neighbour with the shortest distance (euclidean) to a given position. To filter not allowed
+    /// positions, a specific filter is provided.
+    ///
+    /// It is generally not allowed for ghosts to turn around, so the position behind the ghost is always filtered. However,
+    /// if due to some circumstances (like bad map design) a ghost has no other way to go, we allow the poor soul to
+    /// turn around.
+    fn get_nearest_neighbour_to(
+        &self,
+        target: Pos,
+    ) -> Neighbour {
+        let ghost_pos = Pos::from_vec3(self.components.transform.translation);
+        let opposite_dir = self.components.direction.opposite();
+
+        ghost_pos
+            .neighbours_with_directions()
+            .into_iter()
+            .filter(|(_, dir)| *dir != opposite_dir)
+            .filter(|(pos, _)| !self.wall_positions.contains(pos))
+            .filter(|(_, dir)| {
+                if self.is_on_one_way(ghost_pos) {
+                    *dir == Left || *dir == Right
+                } else {
+                    true
+                }
+            })
+            .min_by(|n_a, n_b| minimal_distance_to_neighbours(&target, n_a, n_b))
+            .unwrap_or_else(|| (ghost_pos.neighbour_in_direction(opposite_dir), opposite_dir))
+    }
+
+    fn is_on_one_way(
+        &self,
+        pos: Pos,
+    ) -> bool {
+        self.one_ways.contains(&pos)
+    }
+
+    fn set_target_to_neighbour(
+        &mut self,
+        neighbour: Neighbour,
+    ) {
+        *self.components.direction = neighbour.1;
+        self.components.target.set(neighbour.0.to_vec3(0.0));
+    }
+
+    fn get_spawn(
+        &self,
+        slot: GhostSlot,
+    ) -> &GhostSpawn {
+        self.ghost_spawns.get(&slot).unwrap()
+    }
+}
+
+fn get_ghost_positions(query: &Query<TargetComponents, Without<Pacman>>) -> Vec<(Ghost, Pos)> {
+    query
+        .iter()
+        .map(|comps| (*comps.ghost, Pos::from_vec3(comps.transform.translation)))
+        .collect()
+}
+
+fn minimal_distance_to_neighbours(
+    big_target: &Pos,
+    neighbour_a: &Neighbour,
+    neighbour_b: &Neighbour,
+) -> Ordering {
+    minimal_distance_to_positions(big_target, &neighbour_a.0, &neighbour_b.0)
+}
+
+fn minimal_distance_to_positions(
+    big_target: &Pos,
+    position_a: &Pos,
+    position_b: &Pos,
+) -> Ordering {
+    big_target
+        .distance(position_a)
+        .partial_cmp(&big_target.distance(position_b))
+        .unwrap()
+}
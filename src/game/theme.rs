@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use crate::core::prelude::*;
+
+pub(in crate::game) struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                apply_theme.run_if(resource_exists_and_changed::<Theme>)
+            )
+        ;
+    }
+}
+
+/// When the theme changed, show everything with the sprites of the new theme.
+fn apply_theme(
+    theme_assets: ThemeAssets,
+    mut animated_query: Query<(&ThemedAnimations, &mut Animations, &mut Sprite)>,
+    mut sprite_query: Query<(&ThemedImage, &mut Sprite), Without<Animations>>,
+    mut image_node_query: Query<(&ThemedImage, &mut ImageNode)>,
+) {
+    for (themed_animations, mut animations, mut sprite) in &mut animated_query {
+        animations.replace_animations(theme_assets.animations(themed_animations));
+        animations.current().apply_to(&mut sprite);
+    }
+
+    for (themed_image, mut sprite) in &mut sprite_query {
+        theme_assets.animation(themed_image).apply_to(&mut sprite);
+    }
+
+    for (themed_image, mut image_node) in &mut image_node_query {
+        let themed_node = theme_assets.image_node(themed_image);
+        image_node.image = themed_node.image;
+        image_node.texture_atlas = themed_node.texture_atlas;
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use crate::core::prelude::*;
+
+pub(in crate::game) struct TileGraphPlugin;
+
+impl Plugin for TileGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TileGraph>()
+            .init_resource::<HomeDistanceField>()
+            .add_systems(OnEnter(Game(Start)), (create_tile_graph, create_home_distance_field).chain())
+        ;
+    }
+}
+
+/// Create the tile graph from the spawned maze. The maze does not change while the game runs,
+/// so this only has to be done once.
+fn create_tile_graph(
+    mut commands: Commands,
+    map_query: Query<&Map>,
+    wall_query: Query<&Transform, With<Wall>>,
+    tunnel_query: Query<(&Tunnel, &Tiles)>,
+) -> Result {
+    let map = map_query.single()?;
+
+    commands.insert_resource(TileGraph::new(
+        map,
+        wall_query.iter().map(|transform| Pos::from_vec3(transform.translation)),
+        tunnel_query.iter().map(|(tunnel, tiles)| (tiles.to_pos(), tunnel.index, tunnel.direction)),
+    ));
+
+    Ok(())
+}
+
+/// Eaten ghosts follow this field home, so it must exist before the first ghost gets eaten.
+fn create_home_distance_field(
+    mut commands: Commands,
+    tile_graph: Res<TileGraph>,
+    ghost_house_query: Query<&GhostHouseLayout>,
+) -> Result {
+    let ghost_house = ghost_house_query.single()?;
+    commands.insert_resource(HomeDistanceField(tile_graph.distance_field(ghost_house.entrance_positions)));
+    Ok(())
+}
@@ -2,6 +2,7 @@ use crate::core::prelude::*;
 use bevy::prelude::PositionType::Absolute;
 use bevy::prelude::Val::Percent;
 use bevy::prelude::*;
+use crate::game::ui::MazeAnchor;
 
 pub(super) struct BottomUIPlugin;
 
@@ -12,7 +13,7 @@ impl Plugin for BottomUIPlugin {
     ) {
         app.add_systems(OnEnter(Game(Start)), spawn_bottom_ui)
             .add_systems(Update, (update_lives, update_fruits).run_if(in_game))
-            .add_systems(OnExit(Game(GameOver)), despawn_bottom_ui);
+            .add_systems(CleanUpGame, despawn_bottom_ui);
     }
 }
 
@@ -38,29 +39,25 @@ struct UIFruit;
 
 fn spawn_bottom_ui(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
     lives: Res<Lives>,
     level: Res<Level>,
-    specs_per_level: Res<SpecsPerLevel>,
+    ruleset: Res<Ruleset>,
 ) {
     let bottom_ui = commands
         .spawn((
             Name::new("BottomUI"),
             BottomUI,
+            MazeAnchor::Below,
             Node {
-                width: Percent(40.0),
-                height: Percent(10.0),
                 justify_content: JustifyContent::SpaceBetween,
-                top: Percent(90.0),
-                left: Percent(30.0),
-                position_type: Absolute,
                 ..default()
             },
         ))
         .id();
 
-    let ui_lives = spawn_ui_lives(&mut commands, &asset_server, &lives);
-    let ui_fruits = spawn_ui_fruits(&mut commands, &asset_server, &level, &specs_per_level);
+    let ui_lives = spawn_ui_lives(&mut commands, &theme_assets, &lives);
+    let ui_fruits = spawn_ui_fruits(&mut commands, &theme_assets, &level, &ruleset);
 
     commands
         .entity(bottom_ui)
@@ -69,7 +66,7 @@ fn spawn_bottom_ui(
 
 fn spawn_ui_lives(
     commands: &mut Commands,
-    asset_server: &AssetServer,
+    theme_assets: &ThemeAssets,
     lives: &Lives,
 ) -> Entity {
     let ui_lives = commands
@@ -88,7 +85,7 @@ fn spawn_ui_lives(
         .id();
 
     let ui_live_vec = (0..**lives)
-        .map(|i| spawn_ui_live(i, commands, asset_server))
+        .map(|i| spawn_ui_live(i, commands, theme_assets))
         .collect::<Vec<_>>();
 
     commands.entity(ui_lives).add_children(&ui_live_vec);
@@ -98,9 +95,8 @@ fn spawn_ui_lives(
 fn spawn_ui_live(
     index: usize,
     commands: &mut Commands,
-    asset_server: &AssetServer,
+    theme_assets: &ThemeAssets,
 ) -> Entity {
-    let image = asset_server.load("textures/pacman/pacman_life.png");
     commands
         .spawn((
             Name::new("UILive"),
@@ -112,16 +108,17 @@ fn spawn_ui_live(
                 position_type: Absolute,
                 ..default()
             },
-            ImageNode::new(image.clone()),
+            theme_assets.image_node("pacman_life"),
+            ThemedImage("pacman_life".to_string()),
         ))
         .id()
 }
 
 fn spawn_ui_fruits(
     commands: &mut Commands,
-    asset_server: &AssetServer,
+    theme_assets: &ThemeAssets,
     level: &Level,
-    specs_per_level: &SpecsPerLevel,
+    ruleset: &Ruleset,
 ) -> Entity {
     let ui_fruits = commands
         .spawn((
@@ -139,10 +136,10 @@ fn spawn_ui_fruits(
         ))
         .id();
 
-    let fruits_to_display = get_fruits_to_display(level, specs_per_level);
+    let fruits_to_display = get_fruits_to_display(level, ruleset);
 
     for (i, fruit) in fruits_to_display.into_iter().enumerate() {
-        let ui_fruit = spawn_ui_fruit(commands, asset_server, i, fruit);
+        let ui_fruit = spawn_ui_fruit(commands, theme_assets, i, fruit);
         commands.entity(ui_fruits).add_children(&[ui_fruit]);
     }
 
@@ -151,22 +148,22 @@ fn spawn_ui_fruits(
 
 fn get_fruits_to_display(
     level: &Level,
-    specs_per_level: &SpecsPerLevel,
+    ruleset: &Ruleset,
 ) -> Vec<Fruit> {
     let border = level.checked_sub(6).unwrap_or(1).max(1);
     (border..=**level)
         .rev()
-        .map(|i| specs_per_level.get_for(&Level(i)).fruit_to_spawn)
+        .map(|i| ruleset.fruit.fruit_for_level(&Level(i)))
         .collect()
 }
 
 fn spawn_ui_fruit(
     commands: &mut Commands,
-    asset_server: &AssetServer,
+    theme_assets: &ThemeAssets,
     index: usize,
     fruit: Fruit,
 ) -> Entity {
-    let image = get_texture_for_fruit(&fruit, asset_server);
+    let sprite_name = get_sprite_name_for_fruit(&fruit);
     let left_percent = 100.0 - index as f32 * (100.0 / 7.0) - 100.0 / 7.0;
 
     commands
@@ -180,7 +177,8 @@ fn spawn_ui_fruit(
                 position_type: Absolute,
                 ..default()
             },
-            ImageNode::new(image),
+            theme_assets.image_node(sprite_name),
+            ThemedImage(sprite_name.to_string()),
         ))
         .id()
 }
@@ -189,7 +187,7 @@ fn spawn_ui_fruit(
 fn update_lives(
     mut commands: Commands,
     lives: Res<Lives>,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
     bottom_ui_query: Query<Entity, With<BottomUI>>,
     ui_lives_query: Query<Entity, With<UILives>>,
 ) {
@@ -199,7 +197,7 @@ fn update_lives(
         }
 
         for bottom_ui in &bottom_ui_query {
-            let ui_lives = spawn_ui_lives(&mut commands, &asset_server, &lives);
+            let ui_lives = spawn_ui_lives(&mut commands, &theme_assets, &lives);
             commands.entity(bottom_ui).add_children(&[ui_lives]);
         }
     }
@@ -207,9 +205,9 @@ fn update_lives(
 
 fn update_fruits(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    theme_assets: ThemeAssets,
     level: Res<Level>,
-    specs_per_level: Res<SpecsPerLevel>,
+    ruleset: Res<Ruleset>,
     bottom_ui_query: Query<Entity, With<BottomUI>>,
     ui_fruits_query: Query<Entity, With<UIFruits>>,
 ) {
@@ -219,7 +217,7 @@ fn update_fruits(
         }
 
         for bottom_ui in &bottom_ui_query {
-            let ui_fruits = spawn_ui_fruits(&mut commands, &asset_server, &level, &specs_per_level);
+            let ui_fruits = spawn_ui_fruits(&mut commands, &theme_assets, &level, &ruleset);
             commands.entity(bottom_ui).add_children(&[ui_fruits]);
         }
     }
@@ -1,4 +1,5 @@
 use crate::core::prelude::*;
+use crate::game::ui::MazeAnchor;
 use bevy::prelude::Val::Percent;
 use bevy::prelude::*;
 
@@ -26,40 +27,47 @@ fn spawn_screens(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) {
+    let font = TextFont {
+        font: asset_server.load(FONT),
+        font_size: 20.0,
+        ..default()
+    };
+
     commands.spawn((
         Name::new("GameOverScreen"),
         GameOverScreen,
+        MazeAnchor::Maze,
         Node {
-            position_type: PositionType::Absolute,
-            left: Percent(42.5),
-            top: Percent(55.0),
-            ..default()
-        },
-        Text::new("GAME OVER"),
-        TextFont {
-            font: asset_server.load(FONT),
-            font_size: 20.0,
+            justify_content: JustifyContent::Center,
             ..default()
         },
-        TextColor(Color::srgb(1.0, 0.0, 0.0)),
+        children![(
+            Node {
+                top: Percent(55.0),
+                ..default()
+            },
+            LocalizedText::new("game_over"),
+            Text::default(),
+            font.clone(),
+            TextColor(Color::srgb(1.0, 0.0, 0.0)),
+        )]
     ));
 
     commands.spawn((
         Name::new("RestartGameScreen"),
         RestartGameScreen,
+        MazeAnchor::Below,
         Node {
-            position_type: PositionType::Absolute,
-            left: Percent(37.5),
-            top: Percent(96.0),
-            ..default()
-        },
-        Text::new("Press R to restart"),
-        TextFont {
-            font: asset_server.load(FONT),
-            font_size: 20.0,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::End,
             ..default()
         },
-        TextColor(Color::srgb(1.0, 0.0, 0.0)),
+        children![(
+            LocalizedText::new("press_r_to_restart"),
+            Text::default(),
+            font,
+            TextColor(Color::srgb(1.0, 0.0, 0.0)),
+        )]
     ));
 }
 
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy::prelude::Val::{Percent, Px};
+use crate::asset_preload::AssetPreloadUpdate;
+use crate::core::prelude::*;
+
+pub(super) struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                OnEnter(Setup(PreloadAssets)),
+                spawn_loading_screen
+            )
+            .add_systems(
+                Update,
+                update_loading_screen.run_if(in_state(Setup(PreloadAssets)))
+            )
+            .add_systems(
+                OnExit(Setup(PreloadAssets)),
+                despawn_loading_screen
+            )
+        ;
+    }
+}
+
+/// Parent of the whole loading screen, including its camera.
+///
+/// The texts are not localized, as the locales are not loaded yet.
+#[derive(Component)]
+struct LoadingScreen;
+
+/// The part of the progress bar which fills up while the assets load
+#[derive(Component)]
+struct ProgressBarFill;
+
+/// Shows how many assets are loaded
+#[derive(Component)]
+struct ProgressText;
+
+/// Lists the assets which could not be loaded
+#[derive(Component)]
+struct FailureList;
+
+fn spawn_loading_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let font = TextFont {
+        font: asset_server.load(FONT),
+        font_size: 20.0,
+        ..default()
+    };
+
+    // the game camera is spawned with the maze, so the loading screen needs its own
+    commands.spawn((
+        Name::new("LoadingScreenCamera"),
+        LoadingScreen,
+        Camera2d,
+    ));
+
+    commands.spawn((
+        Name::new("LoadingScreen"),
+        LoadingScreen,
+        Node {
+            width: Percent(100.0),
+            height: Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Px(20.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new("LOADING"),
+                font.clone(),
+                TextColor(Color::srgb(1.0, 1.0, 0.0)),
+            ),
+            (
+                Name::new("ProgressBar"),
+                Node {
+                    width: Percent(60.0),
+                    height: Px(20.0),
+                    border: UiRect::all(Px(2.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::WHITE),
+                children![(
+                    ProgressBarFill,
+                    Node {
+                        width: Percent(0.0),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(1.0, 1.0, 0.0)),
+                )]
+            ),
+            (
+                ProgressText,
+                Text::new(""),
+                font,
+                TextColor(Color::WHITE),
+            ),
+            (
+                FailureList,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    max_width: Percent(90.0),
+                    row_gap: Px(8.0),
+                    ..default()
+                },
+            ),
+        ]
+    ));
+}
+
+/// Show the progress of the preload. If an asset could not be loaded, list every failed asset and the
+/// reason, as the game cannot start without them.
+///
+/// The failures are written with bevy's default font, as the font of the game might be one of them.
+fn update_loading_screen(
+    mut commands: Commands,
+    mut update_reader: MessageReader<AssetPreloadUpdate>,
+    mut fill_query: Query<&mut Node, With<ProgressBarFill>>,
+    mut text_query: Query<(&mut Text, &mut TextFont, &mut TextColor), With<ProgressText>>,
+    failure_list_query: Query<Entity, With<FailureList>>,
+    mut num_listed_failures: Local<usize>,
+) {
+    let Some(update) = update_reader.read().last() else {
+        return;
+    };
+
+    let progress = update.num_loaded as f32 / update.num_loading.max(1) as f32;
+
+    for mut node in &mut fill_query {
+        node.width = Percent(progress * 100.0);
+    }
+
+    for (mut text, mut font, mut color) in &mut text_query {
+        if update.failed.is_empty() {
+            **text = format!("{} / {}", update.num_loaded, update.num_loading);
+        } else {
+            **text = format!("{} ASSETS COULD NOT BE LOADED", update.failed.len());
+            font.font = default();
+            *color = TextColor(Color::srgb(1.0, 0.0, 0.0));
+        }
+    }
+
+    if update.failed.len() == *num_listed_failures {
+        return;
+    }
+
+    for failed in update.failed.iter().skip(*num_listed_failures) {
+        error!("could not load {}: {}", failed.path, failed.error);
+    }
+
+    *num_listed_failures = update.failed.len();
+
+    for failure_list in &failure_list_query {
+        commands
+            .entity(failure_list)
+            .despawn_related::<Children>()
+            .with_children(|parent| {
+                for failed in &update.failed {
+                    parent.spawn((
+                        Text::new(format!("{}: {}", failed.path, failed.error)),
+                        TextFont {
+                            font_size: 10.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.0, 0.0)),
+                    ));
+                }
+            });
+    }
+}
+
+fn despawn_loading_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<LoadingScreen>>,
+) {
+    for e in &query {
+        commands.entity(e).despawn();
+    }
+}
@@ -1,12 +1,18 @@
 use bevy::prelude::*;
+use bevy::prelude::Val::Px;
+use crate::core::prelude::*;
 use crate::game::ui::bottom::BottomUIPlugin;
 use crate::game::ui::game_over_screen::GameOverScreenPlugin;
+use crate::game::ui::loading_screen::LoadingScreenPlugin;
+use crate::game::ui::options_screen::OptionsScreenPlugin;
 use crate::game::ui::ready_screen::ReadyScreenPlugin;
 use crate::game::ui::top::TopUIPlugin;
 
 mod top;
 mod bottom;
 mod game_over_screen;
+mod loading_screen;
+pub(in crate::game) mod options_screen;
 mod ready_screen;
 
 pub(super) struct UIPlugin;
@@ -18,9 +24,48 @@ impl Plugin for UIPlugin {
                 TopUIPlugin,
                 BottomUIPlugin,
                 ReadyScreenPlugin,
-                GameOverScreenPlugin
+                GameOverScreenPlugin,
+                OptionsScreenPlugin,
+                LoadingScreenPlugin
             ))
+            .add_systems(PostUpdate, anchor_to_maze.before(bevy::ui::UiSystems::Layout))
         ;
     }
 }
 
+/// Places a UI node relative to the maze, so it stays in place no matter how big the window is.
+/// The node covers the whole anchor area; its children can be placed with percentages inside it.
+#[derive(Component, Copy, Clone)]
+enum MazeAnchor {
+    /// The area above the maze, where the scores are
+    Above,
+    /// The area on top of the maze itself
+    Maze,
+    /// The area beneath the maze, where the lives and fruits are
+    Below,
+}
+
+/// Move and resize all anchored nodes to their area. Only newly spawned nodes are updated
+/// unless the maze moved on the screen.
+fn anchor_to_maze(
+    maze_viewport: Res<MazeViewport>,
+    mut query: Query<(Ref<MazeAnchor>, &mut Node)>,
+) {
+    for (anchor, mut node) in &mut query {
+        if !maze_viewport.is_changed() && !anchor.is_added() {
+            continue;
+        }
+
+        let area = match *anchor {
+            MazeAnchor::Above => maze_viewport.above(),
+            MazeAnchor::Maze => maze_viewport.maze,
+            MazeAnchor::Below => maze_viewport.below(),
+        };
+
+        node.position_type = PositionType::Absolute;
+        node.left = Px(area.min.x);
+        node.top = Px(area.min.y);
+        node.width = Px(area.width());
+        node.height = Px(area.height());
+    }
+}
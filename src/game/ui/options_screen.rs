@@ -0,0 +1,309 @@
+use crate::core::prelude::*;
+use crate::sprite_sheet::SpriteSheets;
+use bevy::prelude::*;
+use std::path::Path;
+
+pub(super) struct OptionsScreenPlugin;
+
+impl Plugin for OptionsScreenPlugin {
+    fn build(
+        &self,
+        app: &mut App,
+    ) {
+        app.init_resource::<OptionsMenu>()
+            .add_systems(
+                Update,
+                (
+                    toggle_options_screen,
+                    navigate_options.run_if(|menu: Res<OptionsMenu>| menu.open),
+                    update_entry_texts.run_if(
+                        resource_changed::<OptionsMenu>
+                            .or(resource_changed::<AudioSettings>)
+                            .or(resource_exists_and_changed::<Theme>)
+                            .or(resource_exists_and_changed::<Locale>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_game),
+            );
+    }
+}
+
+/// How much the volume of a channel changes with one key press
+const VOLUME_STEP: f32 = 0.1;
+/// Every entry of the options screen, from top to bottom
+const ENTRIES: [OptionsEntry; 7] = [
+    OptionsEntry::Volume(AudioChannel::Master),
+    OptionsEntry::Volume(AudioChannel::Music),
+    OptionsEntry::Volume(AudioChannel::Effects),
+    OptionsEntry::Mute,
+    OptionsEntry::Theme,
+    OptionsEntry::Language,
+    OptionsEntry::SavedGame,
+];
+
+/// If the options screen is open and which of its entries is selected.
+#[derive(Resource, Default)]
+pub(in crate::game) struct OptionsMenu {
+    open: bool,
+    selected: usize,
+}
+
+/// Parent of the whole options screen
+#[derive(Component)]
+struct OptionsScreen;
+
+/// A line of the options screen.
+#[derive(Component, Copy, Clone, Eq, PartialEq)]
+enum OptionsEntry {
+    Volume(AudioChannel),
+    Mute,
+    Theme,
+    Language,
+    SavedGame,
+}
+
+impl OptionsEntry {
+    /// The message id of the name of the entry, see [Locale]
+    fn message_id(&self) -> &'static str {
+        match self {
+            OptionsEntry::Volume(channel) => channel.message_id(),
+            OptionsEntry::Mute => "mute",
+            OptionsEntry::Theme => "theme",
+            OptionsEntry::Language => "language",
+            OptionsEntry::SavedGame => "saved_game",
+        }
+    }
+}
+
+/// A run condition which returns true if the options screen is closed. The keys which navigate through
+/// the options must not move pacman.
+pub(in crate::game) fn options_screen_closed(menu: Res<OptionsMenu>) -> bool {
+    !menu.open
+}
+
+/// Open or close the options screen with escape. The game pauses while the screen is open.
+fn toggle_options_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<OptionsMenu>,
+    mut time: ResMut<Time<Virtual>>,
+    query: Query<Entity, With<OptionsScreen>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    menu.open = !menu.open;
+
+    if menu.open {
+        time.pause();
+        spawn_options_screen(&mut commands, &asset_server);
+    } else {
+        close_options_screen(&mut commands, &mut time, &query);
+    }
+}
+
+fn close_options_screen(
+    commands: &mut Commands,
+    time: &mut Time<Virtual>,
+    query: &Query<Entity, With<OptionsScreen>>,
+) {
+    time.unpause();
+
+    for e in query {
+        commands.entity(e).despawn();
+    }
+}
+
+fn spawn_options_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+) {
+    let font = TextFont {
+        font: asset_server.load(FONT),
+        font_size: 20.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Name::new("OptionsScreen"),
+            OptionsScreen,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            GlobalZIndex(1),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new("options"),
+                Text::default(),
+                font.clone(),
+                TextColor(Color::srgb(1.0, 1.0, 0.0)),
+            ));
+
+            for entry in ENTRIES {
+                parent.spawn((
+                    entry,
+                    Text::new(""),
+                    font.clone(),
+                    TextColor(Color::WHITE),
+                ));
+            }
+
+            parent.spawn((
+                LocalizedText::new("esc_to_resume"),
+                Text::default(),
+                font.clone(),
+                TextColor(Color::srgb(1.0, 0.0, 0.0)),
+            ));
+        });
+}
+
+/// Select an entry with up and down. Left and right change the volume of the selected
+/// channel, toggle the mute or switch to the previous or next theme or language.
+/// Enter closes the screen and continues the saved game, if there is one.
+#[allow(clippy::too_many_arguments)]
+fn navigate_options(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    save_game_path: Option<Res<SaveGamePath>>,
+    mut resume_writer: MessageWriter<ResumeSavedGame>,
+    mut time: ResMut<Time<Virtual>>,
+    screen_query: Query<Entity, With<OptionsScreen>>,
+    mut menu: ResMut<OptionsMenu>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut theme: ResMut<Theme>,
+    themes: Res<Assets<Theme>>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    sprite_sheets: Res<SpriteSheets>,
+    mut locale: ResMut<Locale>,
+    locales: Res<Assets<Locale>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        menu.selected = (menu.selected + ENTRIES.len() - 1) % ENTRIES.len();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        menu.selected = (menu.selected + 1) % ENTRIES.len();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Enter)
+        && ENTRIES[menu.selected] == OptionsEntry::SavedGame
+        && saved_game_exists(save_game_path.as_deref())
+    {
+        menu.open = false;
+        close_options_screen(&mut commands, &mut time, &screen_query);
+        resume_writer.write(ResumeSavedGame);
+        return;
+    }
+
+    let step = if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        -VOLUME_STEP
+    } else if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        VOLUME_STEP
+    } else {
+        return;
+    };
+
+    match ENTRIES[menu.selected] {
+        OptionsEntry::Volume(channel) => {
+            let volume = audio_settings.get(channel) + step;
+            audio_settings.set(channel, volume);
+        }
+        OptionsEntry::Mute => audio_settings.muted = !audio_settings.muted,
+        OptionsEntry::Theme => switch_by_name(
+            &mut *theme,
+            &themes,
+            |t| &t.name,
+            |t| t.is_complete(&ghost_behaviours) && t.broken_sprites(&sprite_sheets).is_empty(),
+            step > 0.0,
+        ),
+        OptionsEntry::Language => switch_by_name(&mut *locale, &locales, |l| &l.name, |_| true, step > 0.0),
+        OptionsEntry::SavedGame => (),
+    }
+}
+
+/// Switch to the next (or previous) of all loaded assets of a kind (like themes), ordered by name.
+/// Assets which are not usable (like incomplete themes) are skipped.
+fn switch_by_name<A: Asset + Clone>(
+    current: &mut A,
+    all: &Assets<A>,
+    name: impl Fn(&A) -> &String,
+    usable: impl Fn(&A) -> bool,
+    forward: bool,
+) {
+    let mut all = all
+        .iter()
+        .map(|(_, a)| a)
+        .filter(|a| name(a) == name(current) || usable(a))
+        .collect::<Vec<_>>();
+    all.sort_by(|a, b| name(a).cmp(name(b)));
+
+    let Some(index) = all.iter().position(|a| name(a) == name(current)) else {
+        return;
+    };
+    let next = if forward {
+        (index + 1) % all.len()
+    } else {
+        (index + all.len() - 1) % all.len()
+    };
+
+    if next != index {
+        *current = all[next].clone();
+    }
+}
+
+/// Return if there is a saved game. Without a path, games are never saved.
+fn saved_game_exists(save_game_path: Option<&SaveGamePath>) -> bool {
+    save_game_path.is_some_and(|path| Path::new(&**path).exists())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_entry_texts(
+    menu: Res<OptionsMenu>,
+    audio_settings: Res<AudioSettings>,
+    save_game_path: Option<Res<SaveGamePath>>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+    locale_fonts: LocaleFonts,
+    mut query: Query<(&OptionsEntry, &mut Text, &mut TextFont, &mut TextColor)>,
+) {
+    for (entry, mut text, mut text_font, mut color) in &mut query {
+        let value = match entry {
+            OptionsEntry::Volume(channel) => volume_bar(audio_settings.get(*channel)),
+            OptionsEntry::Mute => locale.text(if audio_settings.muted { "on" } else { "off" }).to_string(),
+            OptionsEntry::Theme => theme.name.to_uppercase(),
+            OptionsEntry::Language => locale.name.to_uppercase(),
+            OptionsEntry::SavedGame => match saved_game_exists(save_game_path.as_deref()) {
+                true => locale.text("enter_to_load").to_string(),
+                false => locale.text("no_saved_game").to_string(),
+            },
+        };
+        let label = format!("{:<10}{value}", locale.text(entry.message_id()));
+
+        text_font.font = locale_fonts.font_for(&label);
+        **text = label;
+        *color = if *entry == ENTRIES[menu.selected] {
+            TextColor(Color::srgb(1.0, 1.0, 0.0))
+        } else {
+            TextColor(Color::WHITE)
+        };
+    }
+}
+
+/// Show the volume as a bar with ten segments.
+fn volume_bar(volume: f32) -> String {
+    let filled = ((volume / VOLUME_STEP).round() as usize).min(10);
+    format!("{}{}", "#".repeat(filled), "-".repeat(10 - filled))
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::prelude::Val::Percent;
 use crate::core::prelude::*;
+use crate::game::ui::MazeAnchor;
 
 pub(super) struct ReadyScreenPlugin;
 
@@ -30,19 +31,25 @@ fn spawn_screen(
     commands.spawn((
         Name::new("ReadyScreen"),
         ReadyScreen,
+        MazeAnchor::Maze,
         Node {
-            position_type: PositionType::Absolute,
-            left: Percent(45.0),
-            top: Percent(55.0),
+            justify_content: JustifyContent::Center,
             ..default()
         },
-        Text::new("Ready!"),
-        TextFont {
-            font: asset_server.load(FONT),
-            font_size: 20.0,
-            ..default()
-        },
-        TextColor(Color::srgb(1.0, 1.0, 0.0)),
+        children![(
+            Node {
+                top: Percent(55.0),
+                ..default()
+            },
+            LocalizedText::new("ready"),
+            Text::default(),
+            TextFont {
+                font: asset_server.load(FONT),
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 0.0)),
+        )]
     ));
 }
 
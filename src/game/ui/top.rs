@@ -2,6 +2,7 @@ use std::time::Duration;
 use bevy::prelude::*;
 use bevy::prelude::Val::Percent;
 use crate::core::prelude::*;
+use crate::game::ui::MazeAnchor;
 
 pub(super) struct TopUIPlugin;
 
@@ -20,7 +21,7 @@ impl Plugin for TopUIPlugin {
                     blink_1_up_label
                 ).run_if(in_game))
             .add_systems(
-                OnExit(Game(GameOver)),
+                CleanUpGame,
                 despawn_top_ui,
             )
         ;
@@ -52,12 +53,9 @@ fn spawn_top_ui(
     commands.spawn((
         Name::new("TopUI"),
         TopUI,
+        MazeAnchor::Above,
         Node {
-            width: Percent(40.0),
-            height: Percent(10.0),
             justify_content: JustifyContent::SpaceBetween,
-            left: Percent(30.0),
-            position_type: PositionType::Absolute,
             ..default()
         },
         children![
@@ -123,7 +121,8 @@ fn high_score_label(
             left: Percent(30.0),
             ..default()
         },
-        Text::new("HIGH SCORE"),
+        LocalizedText::new("high_score"),
+        Text::default(),
         TextFont {
             font,
             font_size: 20.0,
@@ -148,7 +147,8 @@ fn one_up_label(
             top: Percent(10.0),
             ..default()
         },
-        Text::new("1UP"),
+        LocalizedText::new("one_up"),
+        Text::default(),
         TextFont {
             font,
             font_size: 20.0,
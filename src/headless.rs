@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use bevy::app::{AppExit, PluginsState, ScheduleRunnerPlugin};
+use bevy::audio::Volume;
+use bevy::ecs::message::Messages;
+use bevy::prelude::*;
+use bevy::render::RenderPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::tasks::tick_global_task_pools_on_main_thread;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+
+use crate::PacmanGamePlugin;
+use crate::asset_preload::AssetPreloadUpdate;
+use crate::core::prelude::*;
+
+/// The time that passes in the game with every update when running headless.
+pub(crate) const HEADLESS_FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// The default plugins without a window, without a GPU and muted, as nobody is watching anyway.
+pub fn headless_default_plugins() -> impl PluginGroup {
+    DefaultPlugins
+        .set(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            ..default()
+        })
+        .set(RenderPlugin {
+            render_creation: WgpuSettings {
+                backends: None,
+                ..default()
+            }
+            .into(),
+            ..default()
+        })
+        .set(ImagePlugin::default_nearest())
+        .set(AudioPlugin {
+            global_volume: GlobalVolume::new(Volume::SILENT),
+            ..default()
+        })
+        .disable::<WinitPlugin>()
+        .add(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+}
+
+/// Create the whole game headless with a fixed time step. The app is not run, it must be updated
+/// manually instead, so the caller decides when to look at the world and when to stop.
+///
+/// The app is returned once all assets are loaded, or an error if some of them could not be loaded or used.
+pub(crate) fn create_manually_updated_app(configure: impl FnOnce(&mut App)) -> Result<App, PreloadFailed> {
+    let mut app = App::new();
+    app.add_plugins((headless_default_plugins(), PacmanGamePlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(HEADLESS_FRAME_TIME)));
+    configure(&mut app);
+
+    while app.plugins_state() == PluginsState::Adding {
+        tick_global_task_pools_on_main_thread();
+    }
+
+    app.finish();
+    app.cleanup();
+
+    while *app.world().resource::<State<GameState>>().get() == Setup(PreloadAssets) {
+        app.update();
+
+        let messages = app.world().resource::<Messages<AssetPreloadUpdate>>();
+
+        if let Some(update) = messages.iter_current_update_messages().find(|update| !update.failed.is_empty()) {
+            return Err(PreloadFailed(describe_failures(update)));
+        }
+    }
+
+    // the loaded assets might still be rejected, like a ruleset with problems. The reason was already logged
+    if app.should_exit().is_some() {
+        return Err(PreloadFailed(vec!["the loaded assets cannot be played with, see the log for details".to_string()]));
+    }
+
+    Ok(app)
+}
+
+/// The assets could not be loaded, so the game cannot be played.
+#[derive(Clone, Debug)]
+pub struct PreloadFailed(
+    /// Every asset which could not be loaded and why
+    pub Vec<String>,
+);
+
+impl std::fmt::Display for PreloadFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "assets could not be loaded:\n{}", self.0.join("\n"))
+    }
+}
+
+impl std::error::Error for PreloadFailed {}
+
+fn describe_failures(update: &AssetPreloadUpdate) -> Vec<String> {
+    update
+        .failed
+        .iter()
+        .map(|failed| format!("{}: {}", failed.path, failed.error))
+        .collect()
+}
+
+/// Plays the given amount of games with the bot, restarts the game after every game over and
+/// prints a report for every game. Exits the app when all games are played.
+///
+/// Every update advances the game by a fixed time step, so headless games run as fast as possible
+/// and are not affected by the speed of the machine.
+pub struct HeadlessPlugin {
+    pub games: usize,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(
+        &self,
+        app: &mut App,
+    ) {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(HEADLESS_FRAME_TIME)))
+            .insert_resource(SelectedController(PacmanController::Bot))
+            .insert_resource(GameReports {
+                games: self.games,
+                reports: vec![],
+            })
+            .init_resource::<Deaths>()
+            .add_systems(Update, (
+                exit_when_preload_failed,
+                record_deaths.run_if(in_state(Game(Running))),
+            ))
+            .add_systems(OnEnter(Game(GameOver)), report_game_and_restart_or_exit)
+            .add_systems(OnExit(Game(GameOver)), clear_deaths);
+    }
+}
+
+/// Without a window, nobody would see the failure screen and the game would wait forever, so stop right away.
+fn exit_when_preload_failed(
+    mut message_reader: MessageReader<AssetPreloadUpdate>,
+    mut exit_writer: MessageWriter<AppExit>,
+) {
+    let Some(update) = message_reader.read().find(|update| !update.failed.is_empty()) else {
+        return;
+    };
+
+    error!("{}", PreloadFailed(describe_failures(update)));
+    exit_writer.write(AppExit::error());
+}
+
+/// The ghosts which killed pacman in the current game, in order.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct Deaths(Vec<Ghost>);
+
+/// The outcome of a single game.
+struct GameReport {
+    score: usize,
+    level: usize,
+    deaths: Vec<Ghost>,
+}
+
+#[derive(Resource)]
+struct GameReports {
+    /// How many games should be played
+    games: usize,
+    reports: Vec<GameReport>,
+}
+
+fn record_deaths(
+    mut deaths: ResMut<Deaths>,
+    mut message_reader: MessageReader<PacmanWasHit>,
+) {
+    // two ghosts might hit pacman in the same frame, but he only dies once
+    if let Some(PacmanWasHit(ghost)) = message_reader.read().next() {
+        deaths.push(*ghost);
+    }
+}
+
+fn report_game_and_restart_or_exit(
+    score: Res<Score>,
+    level: Res<Level>,
+    deaths: Res<Deaths>,
+    ghost_behaviours: Res<GhostBehaviours>,
+    mut game_reports: ResMut<GameReports>,
+    mut restart_writer: MessageWriter<GameWasRestarted>,
+    mut exit_writer: MessageWriter<AppExit>,
+) {
+    let report = GameReport {
+        score: **score,
+        level: **level,
+        deaths: deaths.clone(),
+    };
+    println!(
+        "game {}: score {}, level {}, killed by {:?}",
+        game_reports.reports.len() + 1,
+        report.score,
+        report.level,
+        report.deaths.last()
+    );
+    game_reports.reports.push(report);
+
+    if game_reports.reports.len() < game_reports.games {
+        restart_writer.write(GameWasRestarted);
+    } else {
+        print_summary(&game_reports.reports, &ghost_behaviours);
+        exit_writer.write(AppExit::Success);
+    }
+}
+
+fn print_summary(
+    reports: &[GameReport],
+    ghost_behaviours: &GhostBehaviours,
+) {
+    let games = reports.len().max(1) as f32;
+    let average_score = reports.iter().map(|r| r.score).sum::<usize>() as f32 / games;
+    let average_level = reports.iter().map(|r| r.level).sum::<usize>() as f32 / games;
+    let best_score = reports.iter().map(|r| r.score).max().unwrap_or_default();
+
+    println!("played {} games", reports.len());
+    println!("average score: {average_score:.1}, best score: {best_score}, average level: {average_level:.1}");
+
+    for ghost in ghost_behaviours.by_release_priority() {
+        let deaths = reports.iter().flat_map(|r| &r.deaths).filter(|g| **g == ghost).count();
+        println!("deaths by {ghost:?}: {deaths}");
+    }
+}
+
+fn clear_deaths(mut deaths: ResMut<Deaths>) {
+    deaths.clear();
+}
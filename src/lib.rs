@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::asset_preload::AssetPreloadPlugin;
+use crate::core::CorePlugin;
+use crate::game::GamePlugin;
+use crate::core::prelude::*;
+use crate::spawn::SpawnPlugin;
+use crate::sprite_sheet::SpriteSheetPlugin;
+
+pub mod assets;
+pub mod core;
+pub mod debug;
+pub mod env;
+pub mod event_log;
+pub mod headless;
+pub mod map_creator;
+pub mod map_validator;
+pub mod replay;
+pub mod simulation;
+mod asset_preload;
+mod game;
+mod spawn;
+mod sprite_sheet;
+
+/// The whole game: loading the assets, spawning the maze and the gameplay itself.
+///
+/// Does not contain the default plugins, so the game can run with a window or headless.
+pub struct PacmanGamePlugin;
+
+impl Plugin for PacmanGamePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ClearColor(Color::srgb(0.0, 0.0, 0.0)))
+            .add_plugins((
+                CorePlugin,
+                GamePlugin,
+                SpawnPlugin,
+                AssetPreloadPlugin::load_given_paths(
+                    Setup(PreloadAssets),
+                    Setup(CreateSpriteSheets),
+                    assets::ALL,
+                ),
+                SpriteSheetPlugin::new(Setup(CreateSpriteSheets), SpawnMaze(SpawnMapScene)),
+            ))
+        ;
+    }
+}
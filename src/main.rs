@@ -1,60 +1,310 @@
+use std::path::{Path, PathBuf};
+
 use bevy::prelude::*;
-use load_assets::load_assets;
-
-use crate::asset_preload::AssetPreloadPlugin;
-use crate::core::CorePlugin;
-use crate::debug::DebugPlugin;
-use crate::game::GamePlugin;
-use core::prelude::*;
-
-use crate::map_creator::create_map;
-use crate::spawn::SpawnPlugin;
-use crate::sprite_sheet::SpriteSheetPlugin;
-
-mod asset_preload;
-mod core;
-mod debug;
-mod game;
-mod map_creator;
-mod spawn;
-mod sprite_sheet;
+use bevy::window::{MonitorSelection, WindowMode};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use pacman::PacmanGamePlugin;
+use pacman::core::prelude::*;
+use pacman::debug::DebugPlugin;
+use pacman::event_log::EventLogPlugin;
+use pacman::headless::{headless_default_plugins, HeadlessPlugin};
+use pacman::map_creator::create_map;
+use pacman::map_validator::validate_map;
+use pacman::replay::{PlayReplayPlugin, RecordReplayPlugin, Replay};
+use pacman::simulation::Simulation;
+
+/// Headless games are aborted after this amount of seconds when simulating.
+const MAX_SECONDS_PER_SIMULATED_GAME: f32 = 60.0 * 60.0;
+
+/// A recreation of the arcade game "Pacman". Without a command, the game is played.
+#[derive(Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    play: PlayArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play the game
+    Play(PlayArgs),
+    /// Create the default map and write it to the map scene in the assets folder
+    CreateMap,
+    /// Check if a map scene contains everything the game needs to build the maze
+    ValidateMap {
+        /// The map scene file, which must be in the assets folder
+        file: PathBuf,
+        /// The asset path of the ruleset whose ghosts need a corner in the map
+        #[arg(long, value_name = "ASSET PATH", default_value = DEFAULT_RULESET_PATH)]
+        ruleset: String,
+    },
+    /// Let the bot play many headless games and print statistics about them
+    Simulate(SimulateArgs),
+    /// Watch a game recorded with play --record. When the recording is over, you take over.
+    /// Like the recording, the replay only runs at normal speed on 60 Hz displays
+    Replay {
+        /// The file the game was recorded to
+        file: String,
+    },
+}
+
+#[derive(Args)]
+struct PlayArgs {
+    /// The level every game starts in
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    level: u64,
+    /// The seed for every random decision, to get the same game with the same inputs
+    #[arg(long)]
+    seed: Option<u64>,
+    /// The asset path of the map scene to play on
+    #[arg(long, value_name = "ASSET PATH", default_value = MAP_SCENE_PATH)]
+    map: String,
+    /// The asset path of the ruleset to play with
+    #[arg(long, value_name = "ASSET PATH", default_value = DEFAULT_RULESET_PATH)]
+    ruleset: String,
+    /// Start in a window, which is the default. F11 switches to fullscreen while playing
+    #[arg(long, conflicts_with_all = ["fullscreen", "headless"])]
+    windowed: bool,
+    /// Start in fullscreen. F11 switches to a window while playing
+    #[arg(long, conflicts_with = "headless")]
+    fullscreen: bool,
+    /// Play without a window, as fast as possible. The bot plays and a report is printed after every game
+    #[arg(long)]
+    headless: bool,
+    /// How many games are played headless before the game exits
+    #[arg(long, default_value_t = 1, requires = "headless")]
+    games: usize,
+    /// Let the bot play instead of the keyboard
+    #[arg(long)]
+    bot: bool,
+    /// Record the first game to this file, to watch it again with the replay command. The game then advances
+    /// 1/60 second every frame, so it only runs at normal speed on 60 Hz displays
+    #[arg(long, value_name = "FILE")]
+    record: Option<String>,
+    /// Write every gameplay event to this file, one JSON object per line
+    #[arg(long, value_name = "FILE")]
+    event_log: Option<String>,
+}
+
+impl PlayArgs {
+    /// The window starts windowed, unless fullscreen was requested. Both flags cannot be given at once.
+    fn window_mode(&self) -> WindowMode {
+        match (self.windowed, self.fullscreen) {
+            (false, true) => WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
+            _ => WindowMode::Windowed,
+        }
+    }
+}
+
+#[derive(Args)]
+struct SimulateArgs {
+    /// How many games are played per ruleset
+    #[arg(long, default_value_t = 1)]
+    games: usize,
+    /// Let the bot play the games. Simulations are always played by the bot, so this is the default
+    #[arg(long)]
+    bot: bool,
+    /// The asset paths of the rulesets to play with, separated by commas
+    #[arg(long, value_name = "ASSET PATHS", value_delimiter = ',', default_value = DEFAULT_RULESET_PATH)]
+    rulesets: Vec<String>,
+    /// The seed for every random decision, to get the same statistics every time
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// The format of the statistics. The per game details are only part of the JSON
+    #[arg(long, value_enum, default_value_t = ReportFormat::Csv)]
+    format: ReportFormat,
+    /// Write the statistics to this file instead of printing them
+    #[arg(long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(ValueEnum, Copy, Clone)]
+enum ReportFormat {
+    Csv,
+    Json,
+}
 
 fn main() {
+    let cli = Cli::parse();
+
+    match cli.command.unwrap_or(Command::Play(cli.play)) {
+        Command::Play(args) => play(args),
+        Command::CreateMap => {
+            let mut app = App::new();
+            app.add_plugins((headless_default_plugins(), PacmanGamePlugin));
+            create_map(&mut app);
+        }
+        Command::ValidateMap { file, ruleset } => validate(&file, &ruleset),
+        Command::Simulate(args) => simulate(args),
+        Command::Replay { file } => replay(&file),
+    }
+}
+
+fn play(args: PlayArgs) {
     let mut app = App::new();
-    app.add_plugins(
+
+    if args.headless {
+        app.add_plugins((
+            headless_default_plugins(),
+            HeadlessPlugin {
+                games: args.games,
+            },
+        ));
+    } else {
+        add_window_plugins(&mut app, args.window_mode());
+
+        // a replay has to start at the beginning of a game, so recorded games are neither saved nor resumed
+        if args.record.is_none() {
+            app.init_resource::<SaveGamePath>();
+        }
+    }
+
+    app.add_plugins(PacmanGamePlugin)
+        .insert_resource(StartingLevel(args.level as usize))
+        .insert_resource(MapScenePath(args.map))
+        .insert_resource(RulesetPath(args.ruleset));
+
+    if args.bot {
+        app.insert_resource(SelectedController(PacmanController::Bot));
+    }
+
+    match (args.record, args.seed) {
+        (Some(path), seed) => {
+            app.add_plugins(RecordReplayPlugin {
+                path,
+                seed: seed.unwrap_or_else(rand::random),
+            });
+        }
+        (None, Some(seed)) => {
+            app.insert_resource(Random::from_seed(seed));
+        }
+        (None, None) => {}
+    }
+
+    if let Some(path) = args.event_log {
+        app.add_plugins(EventLogPlugin { path });
+    }
+
+    app.run();
+}
+
+fn add_window_plugins(
+    app: &mut App,
+    mode: WindowMode,
+) {
+    app.add_plugins((
         DefaultPlugins
             .set(WindowPlugin {
                 primary_window: Some(Window {
                     resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
                     title: "PacMan".to_string(),
-                    resizable: false,
+                    resizable: true,
+                    mode,
                     ..Default::default()
                 }),
                 ..default()
             })
             .set(ImagePlugin::default_nearest()),
-    )
-    .insert_resource(ClearColor(Color::srgb(0.0, 0.0, 0.0)))
-    .add_plugins((
         DebugPlugin,
-        CorePlugin,
-        GamePlugin,
-        SpawnPlugin,
-        AssetPreloadPlugin::load_given_paths(
-            Setup(PreloadAssets),
-            Setup(CreateSpriteSheets),
-            load_assets!(),
-        ),
-        SpriteSheetPlugin::new(Setup(CreateSpriteSheets), SpawnMaze(SpawnMapScene)),
-    ));
-
-    if should_create_map() {
-        create_map(&mut app);
-    } else {
-        app.run();
+    ))
+    .init_resource::<AudioSettingsPath>();
+}
+
+/// Print if the given map scene is valid for the given ruleset, or every problem with it.
+fn validate(
+    file: &Path,
+    ruleset: &str,
+) {
+    let Some(asset_path) = asset_path_of(file) else {
+        exit_with_error(&format!("{} does not exist or is not in the assets folder", file.display()));
+    };
+
+    match validate_map(&asset_path, ruleset, &GhostBehaviours::default()) {
+        Ok(_) => println!("{asset_path} is a valid map"),
+        Err(problems) => {
+            let problems = problems.iter().map(|problem| format!("  - {problem}")).collect::<Vec<_>>().join("\n");
+            exit_with_error(&format!("{asset_path} is not a valid map:\n{problems}"));
+        }
+    }
+}
+
+/// Return the asset path of the given file, if it exists and is in the assets folder.
+fn asset_path_of(file: &Path) -> Option<String> {
+    let file = file.canonicalize().ok()?;
+    let assets = Path::new("assets").canonicalize().ok()?;
+    let asset_path = file.strip_prefix(assets).ok()?;
+
+    Some(asset_path.to_str()?.replace('\\', "/"))
+}
+
+/// Let the bot play the given amount of games with every ruleset and print the statistics,
+/// or write them to the output file.
+fn simulate(args: SimulateArgs) {
+    let simulation = Simulation {
+        rulesets: args.rulesets,
+        games: args.games,
+        seed: args.seed,
+        max_seconds_per_game: MAX_SECONDS_PER_SIMULATED_GAME,
+    };
+
+    let report = simulation.run().unwrap_or_else(|e| exit_with_error(&e.to_string()));
+    let output = match args.format {
+        ReportFormat::Csv => report.to_csv(),
+        ReportFormat::Json => report.to_json(),
+    };
+
+    match args.output {
+        Some(path) => std::fs::write(&path, output).unwrap_or_else(|e| exit_with_error(&format!("could not write {path}: {e}"))),
+        None => print!("{output}"),
     }
 }
 
-fn should_create_map() -> bool {
-    std::env::args().any(|arg| arg.contains("create_map"))
+fn replay(file: &str) {
+    let replay = Replay::load(file).unwrap_or_else(|e| exit_with_error(&format!("could not load the replay {file}: {e}")));
+
+    let mut app = App::new();
+    add_window_plugins(&mut app, WindowMode::Windowed);
+    app.add_plugins((PacmanGamePlugin, PlayReplayPlugin { replay }))
+        .run();
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {message}");
+    std::process::exit(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::window::{MonitorSelection, WindowMode};
+    use clap::{CommandFactory, Parser};
+
+    use crate::{Cli, Command};
+
+    #[test]
+    fn the_command_line_interface_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn the_game_starts_in_a_window_or_in_fullscreen() {
+        let fullscreen = WindowMode::BorderlessFullscreen(MonitorSelection::Primary);
+
+        for (args, mode) in [
+            (vec!["pacman", "play"], WindowMode::Windowed),
+            (vec!["pacman", "play", "--windowed"], WindowMode::Windowed),
+            (vec!["pacman", "play", "--fullscreen"], fullscreen),
+        ] {
+            let Ok(Cli { command: Some(Command::Play(play_args)), .. }) = Cli::try_parse_from(&args) else {
+                panic!("{args:?} should be valid");
+            };
+
+            assert_eq!(play_args.window_mode(), mode);
+        }
+
+        assert!(Cli::try_parse_from(["pacman", "play", "--windowed", "--fullscreen"]).is_err());
+        assert!(Cli::try_parse_from(["pacman", "play", "--fullscreen", "--headless"]).is_err());
+    }
 }
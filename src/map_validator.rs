@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy::app::PluginsState;
+use bevy::asset::LoadState;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::tasks::tick_global_task_pools_on_main_thread;
+use pad::position::Position;
+
+use crate::core::map::MapPlugin;
+use crate::core::prelude::*;
+use crate::headless::headless_default_plugins;
+
+/// How many updates the map scene may take to load before the validation gives up.
+const MAX_UPDATES_TO_LOAD: usize = 10_000;
+
+/// Check if the map scene with the given asset path contains everything the game needs to build the maze
+/// with the given ruleset: exactly one map and one pacman spawn, dots, a ghost house, a fruit spawn,
+/// the scatter corner of every ghost in the ruleset and tunnels which come in pairs. Every tile must lie inside the map.
+///
+/// Returns every problem that was found, so all of them can be fixed at once.
+pub fn validate_map(
+    map_path: &str,
+    ruleset_path: &str,
+    ghost_behaviours: &GhostBehaviours,
+) -> Result<(), Vec<String>> {
+    let ruleset = load_ruleset(ruleset_path).map_err(|e| vec![e])?;
+    let (required_corners, mut problems) = required_corners(&ruleset, ghost_behaviours);
+
+    let mut app = create_app();
+    let scene = load_scene(&mut app, map_path).map_err(|e| vec![e])?;
+
+    scene
+        .write_to_world(app.world_mut(), &mut EntityHashMap::default())
+        .map_err(|e| vec![format!("the map scene could not be spawned: {e}")])?;
+
+    problems.extend(find_problems(app.world_mut(), &required_corners));
+
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems),
+    }
+}
+
+/// The ruleset is only read, so it is not loaded as an asset.
+fn load_ruleset(path: &str) -> Result<Ruleset, String> {
+    let json = std::fs::read_to_string(Path::new("assets").join(path))
+        .map_err(|e| format!("the ruleset {path} could not be read: {e}"))?;
+    serde_json::from_str(&json).map_err(|e| format!("the ruleset {path} is invalid: {e}"))
+}
+
+/// Return the ghosts whose corners are the scatter targets of the ghosts in the ruleset, and a problem for
+/// every ghost in the ruleset without a behaviour.
+fn required_corners(
+    ruleset: &Ruleset,
+    ghost_behaviours: &GhostBehaviours,
+) -> (Vec<Ghost>, Vec<String>) {
+    let registered_ghosts = ghost_behaviours.by_release_priority();
+    let mut corners = vec![];
+    let mut problems = vec![];
+
+    for ghost in &ruleset.ghosts {
+        if !registered_ghosts.contains(ghost) {
+            problems.push(format!("the ruleset contains {ghost:?}, which has no behaviour"));
+            continue;
+        }
+
+        let corner = ghost_behaviours.get(ghost).scatter_corner;
+
+        if !corners.contains(&corner) {
+            corners.push(corner);
+        }
+    }
+
+    (corners, problems)
+}
+
+/// Only the map types are needed to read the scene, so the game itself is not created.
+fn create_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((headless_default_plugins(), MapPlugin))
+        .register_type::<Pos>()
+        .register_type::<Position>()
+        .register_type::<Dir>()
+        .register_type::<Ghost>();
+
+    while app.plugins_state() == PluginsState::Adding {
+        tick_global_task_pools_on_main_thread();
+    }
+
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn load_scene(
+    app: &mut App,
+    path: &str,
+) -> Result<DynamicScene, String> {
+    let handle: Handle<DynamicScene> = app.world().resource::<AssetServer>().load(path.to_string());
+
+    for _ in 0..MAX_UPDATES_TO_LOAD {
+        app.update();
+
+        match app.world().resource::<AssetServer>().load_state(&handle) {
+            LoadState::Loaded => {
+                return app
+                    .world_mut()
+                    .resource_mut::<Assets<DynamicScene>>()
+                    .remove(&handle)
+                    .ok_or_else(|| "the map scene was loaded, but is gone".to_string())
+            }
+            LoadState::Failed(e) => return Err(format!("the map scene could not be loaded: {e}")),
+            _ => {}
+        }
+    }
+
+    Err("the map scene took too long to load".to_string())
+}
+
+fn find_problems(
+    world: &mut World,
+    required_corners: &[Ghost],
+) -> Vec<String> {
+    let maps = world
+        .query::<&Map>()
+        .iter(world)
+        .map(|map| Map { width: map.width, height: map.height })
+        .collect::<Vec<_>>();
+
+    let [map] = &maps[..] else {
+        // without exactly one map, there is no maze to check
+        return vec![format!("there should be exactly one map, but there are {}", maps.len())];
+    };
+
+    let mut problems = vec![];
+
+    match count::<PacmanSpawn>(world) {
+        1 => {}
+        spawns => problems.push(format!("there should be exactly one pacman spawn, but there are {spawns}")),
+    }
+
+    if count::<DotSpawn>(world) == 0 {
+        problems.push("there are no dots".to_string());
+    }
+
+    if count::<GhostHouseArea>(world) == 0 {
+        problems.push("there is no ghost house".to_string());
+    }
+
+    if count::<FruitSpawn>(world) == 0 {
+        problems.push("there is no fruit spawn".to_string());
+    }
+
+    let corners = world.query::<&GhostCorner>().iter(world).map(|corner| **corner).collect::<Vec<_>>();
+
+    for ghost in required_corners {
+        if !corners.contains(ghost) {
+            problems.push(format!("{ghost:?} has no corner"));
+        }
+    }
+
+    let mut tunnel_ends = HashMap::<usize, usize>::new();
+
+    for tunnel in world.query::<&Tunnel>().iter(world) {
+        *tunnel_ends.entry(tunnel.index).or_default() += 1;
+    }
+
+    let mut tunnel_ends = tunnel_ends.into_iter().collect::<Vec<_>>();
+    tunnel_ends.sort();
+
+    for (index, ends) in tunnel_ends {
+        if ends != 2 {
+            problems.push(format!("tunnel {index} should have exactly two ends, but has {ends}"));
+        }
+    }
+
+    let tiles_outside = world
+        .query::<&Tiles>()
+        .iter(world)
+        .flat_map(|tiles| match *tiles {
+            Tiles::Single { pos } => vec![pos],
+            Tiles::Double { pos_a, pos_b } => vec![pos_a, pos_b],
+        })
+        .filter(|pos| !map.contains(pos))
+        .count();
+
+    if tiles_outside > 0 {
+        problems.push(format!("{tiles_outside} tiles are outside of the {}x{} map", map.width, map.height));
+    }
+
+    problems
+}
+
+fn count<C: Component>(world: &mut World) -> usize {
+    world.query_filtered::<(), With<C>>().iter(world).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::core::prelude::*;
+    use crate::map_validator::{find_problems, validate_map};
+
+    const CORNERS: [Ghost; 4] = [Blinky, Pinky, Inky, Clyde];
+
+    fn single(x: isize, y: isize) -> Tiles {
+        Tiles::Single { pos: Pos::new(x, y) }
+    }
+
+    /// A tiny 10x10 map with everything the maze needs. Like every map, its rows go from 1 to 10.
+    fn valid_map() -> World {
+        let mut world = World::new();
+        world.spawn(Map { width: 10, height: 10 });
+        world.spawn((PacmanSpawn, Tiles::Double { pos_a: Pos::new(4, 2), pos_b: Pos::new(5, 2) }));
+        world.spawn((DotSpawn, single(1, 1)));
+        world.spawn((EnergizerSpawn, single(1, 8)));
+        world.spawn((FruitSpawn, single(4, 4)));
+        world.spawn((GhostHouseArea::default(), single(4, 6)));
+        world.spawn((Tunnel { index: 0, direction: Left }, single(0, 5)));
+        world.spawn((Tunnel { index: 0, direction: Right }, single(9, 5)));
+
+        for (i, ghost) in CORNERS.into_iter().enumerate() {
+            world.spawn((GhostCorner(ghost), single(i as isize, 10)));
+        }
+
+        world
+    }
+
+    fn problems_of(mut world: World) -> Vec<String> {
+        find_problems(&mut world, &CORNERS)
+    }
+
+    fn despawn_all<C: Component>(world: &mut World) {
+        let entities = world.query_filtered::<Entity, With<C>>().iter(world).collect::<Vec<_>>();
+
+        for entity in entities {
+            world.despawn(entity);
+        }
+    }
+
+    #[test]
+    fn a_complete_map_has_no_problems() {
+        assert!(problems_of(valid_map()).is_empty());
+    }
+
+    #[test]
+    fn the_map_of_the_game_is_valid() {
+        assert_eq!(validate_map(MAP_SCENE_PATH, DEFAULT_RULESET_PATH, &GhostBehaviours::default()), Ok(()));
+    }
+
+    #[test]
+    fn a_map_needs_exactly_one_map_entity() {
+        let mut world = valid_map();
+        world.spawn(Map { width: 5, height: 5 });
+
+        assert_eq!(problems_of(world), vec!["there should be exactly one map, but there are 2"]);
+
+        let mut world = valid_map();
+        despawn_all::<Map>(&mut world);
+
+        assert_eq!(problems_of(world), vec!["there should be exactly one map, but there are 0"]);
+    }
+
+    #[test]
+    fn a_map_needs_exactly_one_pacman_spawn() {
+        let mut world = valid_map();
+        world.spawn((PacmanSpawn, single(7, 2)));
+
+        assert_eq!(problems_of(world), vec!["there should be exactly one pacman spawn, but there are 2"]);
+    }
+
+    #[test]
+    fn a_map_needs_dots() {
+        let mut world = valid_map();
+        despawn_all::<DotSpawn>(&mut world);
+
+        assert_eq!(problems_of(world), vec!["there are no dots"]);
+    }
+
+    #[test]
+    fn a_map_needs_a_ghost_house() {
+        let mut world = valid_map();
+        despawn_all::<GhostHouseArea>(&mut world);
+
+        assert_eq!(problems_of(world), vec!["there is no ghost house"]);
+    }
+
+    #[test]
+    fn a_map_needs_a_fruit_spawn() {
+        let mut world = valid_map();
+        despawn_all::<FruitSpawn>(&mut world);
+
+        assert_eq!(problems_of(world), vec!["there is no fruit spawn"]);
+    }
+
+    #[test]
+    fn a_map_needs_the_corners_of_the_ruleset_only() {
+        let mut world = valid_map();
+        despawn_all::<GhostCorner>(&mut world);
+        world.spawn((GhostCorner(Blinky), single(0, 9)));
+
+        assert_eq!(find_problems(&mut world, &[Blinky]), Vec::<String>::new());
+        assert_eq!(find_problems(&mut world, &[Blinky, Custom(0)]), vec!["Custom(0) has no corner"]);
+    }
+
+    #[test]
+    fn tunnels_need_two_ends() {
+        let mut world = valid_map();
+        world.spawn((Tunnel { index: 1, direction: Left }, single(0, 3)));
+
+        assert_eq!(problems_of(world), vec!["tunnel 1 should have exactly two ends, but has 1"]);
+    }
+
+    #[test]
+    fn tiles_must_be_inside_the_map() {
+        let mut world = valid_map();
+        world.spawn((DotSpawn, single(10, 1)));
+        world.spawn((DotSpawn, single(0, 0)));
+        world.spawn((DotSpawn, single(0, 11)));
+        world.spawn((DotSpawn, Tiles::Double { pos_a: Pos::new(-1, 1), pos_b: Pos::new(0, 1) }));
+
+        assert_eq!(problems_of(world), vec!["4 tiles are outside of the 10x10 map"]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::Path;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::core::prelude::*;
+use crate::headless::HEADLESS_FRAME_TIME;
+
+/// A recorded game with everything needed to play it again exactly like it was played.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    /// The seed of the [Random] the game was played with
+    pub seed: u64,
+    pub level: usize,
+    /// The asset path of the ruleset
+    pub ruleset: String,
+    /// The asset path of the map scene
+    pub map: String,
+    /// The wished direction of pacman in every frame the game was running
+    pub inputs: Vec<Option<Dir>>,
+}
+
+impl Replay {
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).expect("the replay should be serializable");
+        std::fs::write(path, json)
+    }
+}
+
+/// Records the first game into the given file when it is over or quit, so it can be watched again with the [PlayReplayPlugin].
+/// Later games are not recorded, as their random decisions depend on the games before.
+///
+/// The inputs are recorded per frame, so the game advances by the same fixed time step every frame, like headless games.
+/// The game therefore only runs at its normal speed if the display refreshes 60 times per second. It runs faster on
+/// displays with higher refresh rates and slower when frames are dropped. Frames in which the game is paused are not recorded.
+pub struct RecordReplayPlugin {
+    pub path: String,
+    pub seed: u64,
+}
+
+impl Plugin for RecordReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Random::from_seed(self.seed))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(HEADLESS_FRAME_TIME)))
+            .insert_resource(ReplayRecording {
+                path: self.path.clone(),
+                seed: self.seed,
+            })
+            .init_resource::<RecordedInputs>()
+            .add_systems(
+                OnEnter(Game(GameOver)),
+                save_replay.run_if(resource_exists::<ReplayRecording>)
+            )
+            .add_systems(
+                Last,
+                save_replay_when_quit
+                    .run_if(resource_exists::<ReplayRecording>)
+                    .run_if(in_game)
+            )
+        ;
+    }
+}
+
+/// Plays a recorded game again. When the recorded inputs are over, the player takes over with the keyboard.
+///
+/// Like during the recording, the game advances by a fixed time step every frame.
+pub struct PlayReplayPlugin {
+    pub replay: Replay,
+}
+
+impl Plugin for PlayReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Random::from_seed(self.replay.seed))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(HEADLESS_FRAME_TIME)))
+            .insert_resource(StartingLevel(self.replay.level))
+            .insert_resource(RulesetPath(self.replay.ruleset.clone()))
+            .insert_resource(MapScenePath(self.replay.map.clone()))
+            .insert_resource(ReplayInputs::new(self.replay.inputs.clone()))
+            .insert_resource(SelectedController(PacmanController::Replay))
+        ;
+    }
+}
+
+/// Where the game gets recorded to. Removed once the first game was saved, together with the [RecordedInputs].
+#[derive(Resource)]
+struct ReplayRecording {
+    path: String,
+    seed: u64,
+}
+
+fn save_replay(
+    mut commands: Commands,
+    recording: Res<ReplayRecording>,
+    starting_level: Res<StartingLevel>,
+    ruleset_path: Res<RulesetPath>,
+    map_scene_path: Res<MapScenePath>,
+    recorded_inputs: Res<RecordedInputs>,
+) {
+    let replay = Replay {
+        seed: recording.seed,
+        level: **starting_level,
+        ruleset: ruleset_path.0.clone(),
+        map: map_scene_path.0.clone(),
+        inputs: recorded_inputs.to_vec(),
+    };
+
+    match replay.save(&recording.path) {
+        Ok(_) => info!("saved the replay to {}", recording.path),
+        Err(e) => warn!("could not save the replay to {}: {e}", recording.path),
+    }
+
+    commands.remove_resource::<ReplayRecording>();
+    commands.remove_resource::<RecordedInputs>();
+}
+
+fn save_replay_when_quit(
+    mut commands: Commands,
+    mut exit_reader: MessageReader<AppExit>,
+    game_state: Res<State<GameState>>,
+) {
+    // on the game over screen, the replay was already saved
+    if exit_reader.read().count() == 0 || *game_state.get() == Game(GameOver) {
+        return;
+    }
+
+    commands.run_system_cached(save_replay);
+}
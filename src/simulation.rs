@@ -0,0 +1,342 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::core::prelude::*;
+use crate::headless::{create_manually_updated_app, PreloadFailed, HEADLESS_FRAME_TIME};
+
+/// A Monte Carlo simulation: the bot plays many headless games with every given ruleset, and the
+/// statistics of all games are collected in a [SimulationReport].
+///
+/// Used to judge changes to the level specs, the ghost schedule or the ghost house gate with data instead of feel.
+pub struct Simulation {
+    /// The asset paths of the rulesets to play with
+    pub rulesets: Vec<String>,
+    /// How many games are played per ruleset
+    pub games: usize,
+    pub seed: u64,
+    /// A game gets aborted when it runs longer than this amount of (game) seconds, in case the bot
+    /// found a way to never die and never win.
+    pub max_seconds_per_game: f32,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SimulationReport {
+    pub rulesets: Vec<RulesetReport>,
+}
+
+/// The statistics of all games played with one ruleset.
+#[derive(Serialize, Clone, Debug)]
+pub struct RulesetReport {
+    pub ruleset: String,
+    pub average_score: f32,
+    pub best_score: usize,
+    pub average_level: f32,
+    pub deaths_by_ghost: Vec<(Ghost, usize)>,
+    pub ghosts_eaten_per_energizer: f32,
+    /// The share of spawned fruits pacman ate
+    pub fruit_collection_rate: f32,
+    pub levels: Vec<LevelReport>,
+    pub games: Vec<GameStats>,
+}
+
+/// The statistics of a single level over all games which reached it.
+#[derive(Serialize, Clone, Debug)]
+pub struct LevelReport {
+    pub level: usize,
+    /// How many games reached this level
+    pub games: usize,
+    /// The average time pacman was alive and moving in this level
+    pub average_survival_seconds: f32,
+    pub average_deaths: f32,
+    /// The share of games which finished this level
+    pub completion_rate: f32,
+}
+
+/// Everything that happened in a single game.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct GameStats {
+    pub score: usize,
+    pub level: usize,
+    /// If the game took too long and was stopped
+    pub aborted: bool,
+    pub deaths: Vec<Ghost>,
+    pub energizers_eaten: usize,
+    pub ghosts_eaten: usize,
+    pub fruits_spawned: usize,
+    pub fruits_eaten: usize,
+    pub levels: Vec<LevelStats>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct LevelStats {
+    pub level: usize,
+    pub survival_seconds: f32,
+    pub deaths: usize,
+    pub completed: bool,
+}
+
+impl Simulation {
+    /// Run the simulation. Depending on the amount of games this might take a while.
+    pub fn run(&self) -> Result<SimulationReport, PreloadFailed> {
+        Ok(SimulationReport {
+            rulesets: self
+                .rulesets
+                .iter()
+                .map(|ruleset| Ok(RulesetReport::new(ruleset.clone(), self.play_games_with(ruleset)?)))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn play_games_with(
+        &self,
+        ruleset: &str,
+    ) -> Result<Vec<GameStats>, PreloadFailed> {
+        let max_frames = (self.max_seconds_per_game as f64 / HEADLESS_FRAME_TIME) as usize;
+        // the games of every app played so far, as an app gets replaced when a game is aborted
+        let mut games = vec![];
+        let mut aborted_games = 0;
+        let mut app = create_simulation_app(ruleset, self.seed)?;
+        let mut frames = 0;
+
+        while games.len() + app.world().resource::<FinishedGames>().len() < self.games {
+            if is_game_over(&app) {
+                app.world_mut().write_message(GameWasRestarted);
+
+                while is_game_over(&app) {
+                    app.update();
+                }
+
+                frames = 0;
+            }
+
+            app.update();
+            frames += 1;
+
+            if frames > max_frames {
+                // the game cannot be stopped cleanly while it runs, so a new one is created
+                games.extend(take_finished_games(&mut app));
+                games.push(abort_game(app.world_mut()));
+                aborted_games += 1;
+                app = create_simulation_app(ruleset, self.seed + aborted_games)?;
+                frames = 0;
+            }
+        }
+
+        games.extend(take_finished_games(&mut app));
+        Ok(games)
+    }
+}
+
+impl SimulationReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("the report should be serializable")
+    }
+
+    /// Return the statistics per ruleset and level as CSV. The per game details are only part of the JSON.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "ruleset,level,games,average_survival_seconds,average_deaths,completion_rate,\
+            average_score,ghosts_eaten_per_energizer,fruit_collection_rate\n",
+        );
+
+        for report in &self.rulesets {
+            for level in &report.levels {
+                writeln!(
+                    csv,
+                    "{},{},{},{:.2},{:.2},{:.3},{:.1},{:.3},{:.3}",
+                    report.ruleset,
+                    level.level,
+                    level.games,
+                    level.average_survival_seconds,
+                    level.average_deaths,
+                    level.completion_rate,
+                    report.average_score,
+                    report.ghosts_eaten_per_energizer,
+                    report.fruit_collection_rate
+                )
+                .expect("writing to a string cannot fail");
+            }
+        }
+
+        csv
+    }
+}
+
+impl RulesetReport {
+    fn new(
+        ruleset: String,
+        games: Vec<GameStats>,
+    ) -> Self {
+        let num_games = games.len().max(1) as f32;
+        let energizers_eaten = games.iter().map(|g| g.energizers_eaten).sum::<usize>();
+        let ghosts_eaten = games.iter().map(|g| g.ghosts_eaten).sum::<usize>();
+        let fruits_spawned = games.iter().map(|g| g.fruits_spawned).sum::<usize>();
+        let fruits_eaten = games.iter().map(|g| g.fruits_eaten).sum::<usize>();
+
+        let mut deaths_by_ghost: Vec<(Ghost, usize)> = vec![];
+        for ghost in games.iter().flat_map(|g| &g.deaths) {
+            match deaths_by_ghost.iter_mut().find(|(g, _)| g == ghost) {
+                Some((_, deaths)) => *deaths += 1,
+                None => deaths_by_ghost.push((*ghost, 1)),
+            }
+        }
+
+        let max_level = games.iter().map(|g| g.level).max().unwrap_or_default();
+        let levels = (1..=max_level)
+            .map(|level| LevelReport::new(level, &games))
+            .filter(|report| report.games > 0)
+            .collect();
+
+        RulesetReport {
+            ruleset,
+            average_score: games.iter().map(|g| g.score).sum::<usize>() as f32 / num_games,
+            best_score: games.iter().map(|g| g.score).max().unwrap_or_default(),
+            average_level: games.iter().map(|g| g.level).sum::<usize>() as f32 / num_games,
+            deaths_by_ghost,
+            ghosts_eaten_per_energizer: ghosts_eaten as f32 / energizers_eaten.max(1) as f32,
+            fruit_collection_rate: fruits_eaten as f32 / fruits_spawned.max(1) as f32,
+            levels,
+            games,
+        }
+    }
+}
+
+impl LevelReport {
+    fn new(
+        level: usize,
+        games: &[GameStats],
+    ) -> Self {
+        let stats = games
+            .iter()
+            .flat_map(|g| g.levels.iter().filter(|l| l.level == level))
+            .collect::<Vec<_>>();
+        let num_games = stats.len().max(1) as f32;
+
+        LevelReport {
+            level,
+            games: stats.len(),
+            average_survival_seconds: stats.iter().map(|l| l.survival_seconds).sum::<f32>() / num_games,
+            average_deaths: stats.iter().map(|l| l.deaths).sum::<usize>() as f32 / num_games,
+            completion_rate: stats.iter().filter(|l| l.completed).count() as f32 / num_games,
+        }
+    }
+}
+
+impl GameStats {
+    fn current_level_mut(
+        &mut self,
+        level: &Level,
+    ) -> &mut LevelStats {
+        if self.levels.last().is_none_or(|stats| stats.level != **level) {
+            self.levels.push(LevelStats {
+                level: **level,
+                ..default()
+            });
+        }
+
+        self.levels.last_mut().expect("the level was just added")
+    }
+}
+
+/// The stats of the game that is currently played.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct CurrentGame(GameStats);
+
+/// The stats of all games that ended with a game over.
+#[derive(Resource, Deref, DerefMut, Default)]
+struct FinishedGames(Vec<GameStats>);
+
+fn create_simulation_app(
+    ruleset: &str,
+    seed: u64,
+) -> Result<App, PreloadFailed> {
+    create_manually_updated_app(|app| {
+        app.insert_resource(SelectedController(PacmanController::Bot))
+            .insert_resource(RulesetPath(ruleset.to_string()))
+            .insert_resource(Random::from_seed(seed))
+            .init_resource::<CurrentGame>()
+            .init_resource::<FinishedGames>()
+            .add_systems(
+                Update,
+                (
+                    track_survival_time.run_if(in_state(Game(Running))),
+                    count_gameplay_messages.after(ProcessIntersectionsWithPacman),
+                    count_spawned_fruits,
+                ),
+            )
+            .add_systems(OnEnter(Game(LevelTransition)), mark_level_completed)
+            .add_systems(OnEnter(Game(GameOver)), finish_game);
+    })
+}
+
+fn track_survival_time(
+    time: Res<Time>,
+    level: Res<Level>,
+    mut current_game: ResMut<CurrentGame>,
+) {
+    current_game.current_level_mut(&level).survival_seconds += time.delta_secs();
+}
+
+fn count_gameplay_messages(
+    level: Res<Level>,
+    mut current_game: ResMut<CurrentGame>,
+    mut energizer_eaten_reader: MessageReader<EnergizerWasEaten>,
+    mut ghost_eaten_reader: MessageReader<GhostWasEaten>,
+    mut fruit_eaten_reader: MessageReader<FruitWasEaten>,
+    mut pacman_hit_reader: MessageReader<PacmanWasHit>,
+) {
+    current_game.energizers_eaten += energizer_eaten_reader.read().count();
+    current_game.ghosts_eaten += ghost_eaten_reader.read().count();
+    current_game.fruits_eaten += fruit_eaten_reader.read().count();
+
+    // two ghosts might hit pacman in the same frame, but he only dies once
+    if let Some(PacmanWasHit(ghost)) = pacman_hit_reader.read().next() {
+        current_game.deaths.push(*ghost);
+        current_game.current_level_mut(&level).deaths += 1;
+    }
+}
+
+fn count_spawned_fruits(
+    mut current_game: ResMut<CurrentGame>,
+    query: Query<(), Added<Fruit>>,
+) {
+    current_game.fruits_spawned += query.iter().count();
+}
+
+fn mark_level_completed(
+    level: Res<Level>,
+    mut current_game: ResMut<CurrentGame>,
+) {
+    current_game.current_level_mut(&level).completed = true;
+}
+
+fn finish_game(
+    score: Res<Score>,
+    level: Res<Level>,
+    mut current_game: ResMut<CurrentGame>,
+    mut finished_games: ResMut<FinishedGames>,
+) {
+    let mut stats = std::mem::take(&mut **current_game);
+    stats.score = **score;
+    stats.level = **level;
+    finished_games.push(stats);
+}
+
+fn take_finished_games(app: &mut App) -> Vec<GameStats> {
+    std::mem::take(&mut **app.world_mut().resource_mut::<FinishedGames>())
+}
+
+/// Stop the current game and return its stats.
+fn abort_game(world: &mut World) -> GameStats {
+    let mut stats = std::mem::take(&mut **world.resource_mut::<CurrentGame>());
+    stats.score = **world.resource::<Score>();
+    stats.level = **world.resource::<Level>();
+    stats.aborted = true;
+    stats
+}
+
+fn is_game_over(app: &App) -> bool {
+    *app.world().resource::<State<GameState>>().get() == Game(GameOver)
+}
@@ -1,4 +1,3 @@
-use crate::sprite_sheet::{SpriteSheet, SpriteSheets};
 use bevy::prelude::*;
 
 use crate::core::prelude::*;
@@ -16,18 +15,21 @@ impl Plugin for EnhanceGhostHousePlugin {
 
 fn enhance_ghost_house(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    sprite_sheets: Res<SpriteSheets>,
+    theme_assets: ThemeAssets,
+    ruleset: Res<Ruleset>,
+    ghost_behaviours: Res<GhostBehaviours>,
     ghost_house_areas: Query<(&GhostHouseArea, &Tiles)>,
 ) {
     let bottom_left = get_bottom_left(&ghost_house_areas);
     let rotation = get_rotation(&ghost_house_areas);
-    let spawns = create_spawns(rotation, bottom_left);
+    let layout = create_layout(rotation, bottom_left);
+    let spawns = create_spawns(rotation, bottom_left, &ruleset.ghosts, &ghost_behaviours);
 
     let ghost_house = commands
         .spawn((
             Name::new("GhostHouse"),
             GhostHouse,
+            layout,
             Transform::default(),
             Visibility::default(),
         ))
@@ -44,8 +46,7 @@ fn enhance_ghost_house(
         ghost_house,
         bottom_left,
         rotation,
-        &asset_server,
-        &sprite_sheets,
+        &theme_assets,
     );
 }
 
@@ -66,88 +67,154 @@ fn get_rotation(ghost_house_areas: &Query<(&GhostHouseArea, &Tiles)>) -> Rotatio
         .expect("at least one ghost house area should exist")
 }
 
+/// Offsets of the two tiles of a spawn slot (relative to the bottom left of the house) and the direction
+/// a ghost looks at when spawning there.
+type SlotOffsets = ((isize, isize), (isize, isize), Dir);
+
+/// The slot in front of the entrance of a house which is not rotated.
+const ENTRANCE_SLOT: SlotOffsets = ((3, 5), (4, 5), Left);
+
+/// The row through the center of a house which is not rotated. Eaten ghosts are revived in this row.
+const CENTER_ROW: isize = 2;
+
+/// The slots inside a house which is not rotated. The center row comes first (the center, then the left and
+/// right side of it), then the rows below and above it, so the ghosts spread over the whole house.
+///
 /// TODO: wrong. Pinky spawns looking down, Inky and Clyde looking up
-fn create_spawns(
+const HOUSE_SLOTS: [SlotOffsets; MAX_GHOSTS - 1] = [
+    ((3, 2), (4, 2), Up),
+    ((1, 2), (2, 2), Down),
+    ((5, 2), (6, 2), Down),
+    ((3, 1), (4, 1), Up),
+    ((1, 1), (2, 1), Up),
+    ((5, 1), (6, 1), Up),
+    ((3, 3), (4, 3), Down),
+    ((1, 3), (2, 3), Down),
+    ((5, 3), (6, 3), Down),
+];
+
+/// Rotate the offsets of a slot in a house which is not rotated (8 tiles wide and 5 tiles high) to the given rotation.
+fn rotate_slot(
     rotation: Rotation,
-    bottom_left: Pos,
-) -> [GhostSpawn; 4] {
-    [
-        create_blinky_spawn(rotation, bottom_left),
-        create_pinky_spawn(rotation, bottom_left),
-        create_inky_spawn(rotation, bottom_left),
-        create_clyde_spawn(rotation, bottom_left),
-    ]
+    (offsets_0, offsets_1, direction): SlotOffsets,
+) -> SlotOffsets {
+    let rotate_offsets = |(x, y): (isize, isize)| match rotation {
+        D0 => (x, y),
+        D90 => (y, 7 - x),
+        D180 => (7 - x, 4 - y),
+        D270 => (4 - y, x),
+    };
+    let direction = match (rotation, direction) {
+        (D0, dir) => dir,
+        (D90, Up) | (D270, Down) => Right,
+        (D90, Down) | (D270, Up) => Left,
+        (D90, Left) | (D270, Right) => Up,
+        (D90, Right) | (D270, Left) => Down,
+        (D180, dir) => dir.opposite(),
+    };
+
+    (rotate_offsets(offsets_0), rotate_offsets(offsets_1), direction)
 }
 
-fn create_blinky_spawn(
+/// Return the slot in front of the entrance.
+fn entrance_slot(rotation: Rotation) -> SlotOffsets {
+    rotate_slot(rotation, ENTRANCE_SLOT)
+}
+
+/// Return the slots inside the house, see [HOUSE_SLOTS].
+fn house_slots(rotation: Rotation) -> [SlotOffsets; MAX_GHOSTS - 1] {
+    HOUSE_SLOTS.map(|slot| rotate_slot(rotation, slot))
+}
+
+/// Return the offsets of the given spawn slot. The first slot is in front of the entrance, all others
+/// are in the house. The ruleset makes sure there are not more ghosts than slots.
+fn slot_offsets(
     rotation: Rotation,
-    bottom_left: Pos,
-) -> GhostSpawn {
-    match rotation {
-        D0 => create_spawn_with_offsets(bottom_left, (3, 5), (4, 5), BLINKY_Z, Blinky, Left),
-        D90 => create_spawn_with_offsets(bottom_left, (5, 3), (5, 4), BLINKY_Z, Blinky, Up),
-        D180 => create_spawn_with_offsets(bottom_left, (3, -1), (4, -1), BLINKY_Z, Blinky, Right),
-        D270 => create_spawn_with_offsets(bottom_left, (-1, 3), (-1, 4), BLINKY_Z, Blinky, Down),
+    slot: GhostSlot,
+) -> SlotOffsets {
+    match *slot {
+        0 => entrance_slot(rotation),
+        s => house_slots(rotation)[s - 1],
     }
 }
 
-fn create_pinky_spawn(
+/// Return the offsets where the ghost of the given spawn slot is revived after being eaten: the slot in the
+/// center row of the same column. This way, eaten ghosts only move along the center row when entering the house.
+/// The ghost in front of the entrance is revived in the house center.
+fn revive_offsets(
     rotation: Rotation,
-    bottom_left: Pos,
-) -> GhostSpawn {
-    match rotation {
-        D0 => create_spawn_with_offsets(bottom_left, (3, 2), (4, 2), PINKY_Z, Pinky, Up),
-        D90 => create_spawn_with_offsets(bottom_left, (2, 3), (2, 4), PINKY_Z, Pinky, Right),
-        D180 => create_spawn_with_offsets(bottom_left, (3, 2), (4, 2), PINKY_Z, Pinky, Down),
-        D270 => create_spawn_with_offsets(bottom_left, (2, 3), (2, 4), PINKY_Z, Pinky, Left),
-    }
+    slot: GhostSlot,
+) -> SlotOffsets {
+    let ((x_0, _), (x_1, _), direction) = match *slot {
+        0 => HOUSE_SLOTS[0],
+        s => HOUSE_SLOTS[s - 1],
+    };
+
+    rotate_slot(rotation, ((x_0, CENTER_ROW), (x_1, CENTER_ROW), direction))
 }
 
-fn create_inky_spawn(
+fn create_layout(
     rotation: Rotation,
     bottom_left: Pos,
-) -> GhostSpawn {
-    match rotation {
-        D0 => create_spawn_with_offsets(bottom_left, (1, 2), (2, 2), INKY_Z, Inky, Down),
-        D90 => create_spawn_with_offsets(bottom_left, (2, 5), (2, 6), INKY_Z, Inky, Left),
-        D180 => create_spawn_with_offsets(bottom_left, (5, 2), (6, 2), INKY_Z, Inky, Up),
-        D270 => create_spawn_with_offsets(bottom_left, (2, 1), (2, 2), INKY_Z, Inky, Right),
+) -> GhostHouseLayout {
+    let entrance = entrance_slot(rotation);
+    let center = house_slots(rotation)[0];
+    let entrance_positions = slot_positions(bottom_left, entrance);
+
+    GhostHouseLayout {
+        entrance_positions,
+        entrance_coordinates: Vec3::from_positions(entrance_positions.iter(), 0.0),
+        entrance_direction: entrance.2,
+        center_coordinates: Vec3::from_positions(slot_positions(bottom_left, center).iter(), 0.0),
+        exit_direction: center.2,
     }
 }
 
-fn create_clyde_spawn(
+/// Create a spawn for every ghost of the roster. The slots are assigned by release priority,
+/// so the first ghost to leave spawns in front of the entrance.
+///
+/// Every ghost gets revived in the center row of the house, see [revive_offsets].
+fn create_spawns(
     rotation: Rotation,
     bottom_left: Pos,
-) -> GhostSpawn {
-    match rotation {
-        D0 => create_spawn_with_offsets(bottom_left, (5, 2), (6, 2), CLYDE_Z, Clyde, Down),
-        D90 => create_spawn_with_offsets(bottom_left, (2, 1), (2, 2), CLYDE_Z, Clyde, Left),
-        D180 => create_spawn_with_offsets(bottom_left, (1, 2), (2, 2), CLYDE_Z, Clyde, Up),
-        D270 => create_spawn_with_offsets(bottom_left, (2, 5), (2, 6), CLYDE_Z, Clyde, Right),
-    }
+    roster: &[Ghost],
+    ghost_behaviours: &GhostBehaviours,
+) -> Vec<GhostSpawn> {
+    ghost_behaviours
+        .sort_by_release_priority(roster)
+        .into_iter()
+        .enumerate()
+        .map(|(i, ghost)| {
+            let slot = GhostSlot(i);
+            let offsets = slot_offsets(rotation, slot);
+            let z = ghost_behaviours.get(&ghost).z;
+            let positions = slot_positions(bottom_left, offsets);
+            let coordinates = Vec3::from_positions(positions.iter(), z);
+            let respawn_positions = slot_positions(bottom_left, revive_offsets(rotation, slot));
+            let respawn_coordinates = Vec3::from_positions(respawn_positions.iter(), z);
+
+            GhostSpawn {
+                ghost,
+                slot,
+                spawn_direction: offsets.2,
+                positions,
+                coordinates,
+                respawn_coordinates,
+            }
+        })
+        .collect()
 }
 
-fn create_spawn_with_offsets(
+fn slot_positions(
     bottom_left: Pos,
-    offsets_0: (isize, isize),
-    offsets_1: (isize, isize),
-    z: f32,
-    ghost: Ghost,
-    spawn_direction: Dir,
-) -> GhostSpawn {
+    (offsets_0, offsets_1, _): SlotOffsets,
+) -> [Pos; 2] {
     let x = bottom_left.x();
     let y = bottom_left.y();
-    let positions = [
+    [
         Pos::new(x + offsets_0.0, y + offsets_0.1),
         Pos::new(x + offsets_1.0, y + offsets_1.1),
-    ];
-    let coordinates = Vec3::from_positions(positions.iter(), z);
-    GhostSpawn {
-        ghost,
-        spawn_direction,
-        positions,
-        coordinates,
-    }
+    ]
 }
 
 fn spawn_house_walls(
@@ -155,32 +222,29 @@ fn spawn_house_walls(
     ghost_house: Entity,
     bottom_left: Pos,
     rotation: Rotation,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) {
     let top_right = match rotation {
         D0 | D180 => Pos::new(bottom_left.x() + 7, bottom_left.y() + 4),
         _ => Pos::new(bottom_left.x() + 4, bottom_left.y() + 7),
     };
 
-    let corners = spawn_corners(commands, bottom_left, top_right, sprite_sheets);
+    let corners = spawn_corners(commands, bottom_left, top_right, theme_assets);
     let top = spawn_top(
         commands,
         rotation,
         bottom_left,
         top_right,
-        asset_server,
-        sprite_sheets,
+        theme_assets,
     );
-    let bottom = spawn_bottom(commands, rotation, bottom_left, asset_server, sprite_sheets);
-    let left = spawn_left(commands, rotation, bottom_left, asset_server, sprite_sheets);
+    let bottom = spawn_bottom(commands, rotation, bottom_left, theme_assets);
+    let left = spawn_left(commands, rotation, bottom_left, theme_assets);
     let right = spawn_right(
         commands,
         rotation,
         bottom_left,
         top_right,
-        asset_server,
-        sprite_sheets,
+        theme_assets,
     );
     commands.entity(ghost_house).add_children(&corners);
     commands.entity(ghost_house).add_children(&top);
@@ -193,24 +257,26 @@ fn spawn_corners(
     commands: &mut Commands,
     bottom_left: Pos,
     top_right: Pos,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) -> [Entity; 4] {
-    let sheet = sprite_sheets.get_sheet("textures/walls/ghost_house_wall_corner");
+    let sprite = "ghost_house_wall_corner";
     [
         spawn_wall(
             commands,
-            sheet,
+            theme_assets,
+            sprite,
             D0,
             Pos::new(bottom_left.x(), top_right.y()),
         ),
-        spawn_wall(commands, sheet, D90, top_right),
+        spawn_wall(commands, theme_assets, sprite, D90, top_right),
         spawn_wall(
             commands,
-            sheet,
+            theme_assets,
+            sprite,
             D180,
             Pos::new(top_right.x(), bottom_left.y()),
         ),
-        spawn_wall(commands, sheet, D270, bottom_left),
+        spawn_wall(commands, theme_assets, sprite, D270, bottom_left),
     ]
 }
 
@@ -219,34 +285,33 @@ fn spawn_top(
     rotation: Rotation,
     bottom_left: Pos,
     top_right: Pos,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) -> Vec<Entity> {
     let x = bottom_left.x() + 1;
     let y = top_right.y();
-    let sheet = sprite_sheets.get_sheet("textures/walls/ghost_house_wall");
+    let sprite = "ghost_house_wall";
 
     match rotation {
         D0 => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_entrance(commands, asset_server, rotation, Pos::new(x + 2, y)),
-            spawn_entrance(commands, asset_server, rotation, Pos::new(x + 3, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 4, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 5, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_entrance(commands, theme_assets, rotation, Pos::new(x + 2, y)),
+            spawn_entrance(commands, theme_assets, rotation, Pos::new(x + 3, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 4, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 5, y)),
         ],
         D180 => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 2, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 3, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 4, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 5, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 2, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 3, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 4, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 5, y)),
         ],
         _ => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 2, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 2, y)),
         ],
     }
 }
@@ -255,34 +320,33 @@ fn spawn_bottom(
     commands: &mut Commands,
     rotation: Rotation,
     bottom_left: Pos,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) -> Vec<Entity> {
     let x = bottom_left.x() + 1;
     let y = bottom_left.y();
-    let sheet = sprite_sheets.get_sheet("textures/walls/ghost_house_wall");
+    let sprite = "ghost_house_wall";
 
     match rotation {
         D180 => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_entrance(commands, asset_server, rotation, Pos::new(x + 2, y)),
-            spawn_entrance(commands, asset_server, rotation, Pos::new(x + 3, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 4, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 5, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_entrance(commands, theme_assets, rotation, Pos::new(x + 2, y)),
+            spawn_entrance(commands, theme_assets, rotation, Pos::new(x + 3, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 4, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 5, y)),
         ],
         D0 => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 2, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 3, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 4, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 5, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 2, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 3, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 4, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 5, y)),
         ],
         _ => vec![
-            spawn_wall(commands, sheet, rotation, Pos::new(x, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 1, y)),
-            spawn_wall(commands, sheet, rotation, Pos::new(x + 2, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 1, y)),
+            spawn_wall(commands, theme_assets, sprite, rotation, Pos::new(x + 2, y)),
         ],
     }
 }
@@ -291,34 +355,33 @@ fn spawn_left(
     commands: &mut Commands,
     rotation: Rotation,
     bottom_left: Pos,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) -> Vec<Entity> {
     let x = bottom_left.x();
     let y = bottom_left.y() + 1;
-    let sheet = sprite_sheets.get_sheet("textures/walls/ghost_house_wall");
+    let sprite = "ghost_house_wall";
 
     match rotation {
         D270 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_entrance(commands, asset_server, D90, Pos::new(x, y + 2)),
-            spawn_entrance(commands, asset_server, D90, Pos::new(x, y + 3)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 4)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 5)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_entrance(commands, theme_assets, D90, Pos::new(x, y + 2)),
+            spawn_entrance(commands, theme_assets, D90, Pos::new(x, y + 3)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 4)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 5)),
         ],
         D90 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 2)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 3)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 4)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 5)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 2)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 3)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 4)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 5)),
         ],
         D0 | D180 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 2)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 2)),
         ],
     }
 }
@@ -328,54 +391,49 @@ fn spawn_right(
     rotation: Rotation,
     bottom_left: Pos,
     top_right: Pos,
-    asset_server: &AssetServer,
-    sprite_sheets: &SpriteSheets,
+    theme_assets: &ThemeAssets,
 ) -> Vec<Entity> {
     let x = top_right.x();
     let y = bottom_left.y() + 1;
-    let sheet = sprite_sheets.get_sheet("textures/walls/ghost_house_wall");
+    let sprite = "ghost_house_wall";
 
     match rotation {
         D90 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_entrance(commands, asset_server, D90, Pos::new(x, y + 2)),
-            spawn_entrance(commands, asset_server, D90, Pos::new(x, y + 3)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 4)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 5)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_entrance(commands, theme_assets, D90, Pos::new(x, y + 2)),
+            spawn_entrance(commands, theme_assets, D90, Pos::new(x, y + 3)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 4)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 5)),
         ],
         D270 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 2)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 3)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 4)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 5)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 2)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 3)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 4)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 5)),
         ],
         D0 | D180 => vec![
-            spawn_wall(commands, sheet, D90, Pos::new(x, y)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 1)),
-            spawn_wall(commands, sheet, D90, Pos::new(x, y + 2)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 1)),
+            spawn_wall(commands, theme_assets, sprite, D90, Pos::new(x, y + 2)),
         ],
     }
 }
 
 fn spawn_wall(
     commands: &mut Commands,
-    sheet: &SpriteSheet,
+    theme_assets: &ThemeAssets,
+    sprite: &str,
     rotation: Rotation,
     position: Pos,
 ) -> Entity {
-    let animations = Animations::new(
-        [
-            ("idle", Animation::from_texture(sheet.image_at(0))),
-            (
-                "blinking",
-                Animation::from_textures(0.5, true, sheet.images_at([0, 1])),
-            ),
-        ],
+    let themed_animations = ThemedAnimations::new(
+        [("idle", sprite.to_string()), ("blinking", format!("{sprite}_blinking"))],
         "idle",
     );
+    let animations = theme_assets.animations(&themed_animations);
 
     let mut transform = Transform::from_translation(position.to_vec3(0.0));
     transform.rotation = rotation.quat_z();
@@ -385,19 +443,19 @@ fn spawn_wall(
             Name::new("Wall"),
             Wall,
             Sprite {
-                image: animations.current().texture(),
                 custom_size: Some(Vec2::splat(WALL_DIMENSION)),
-                ..default()
+                ..animations.current().sprite()
             },
             transform,
             animations,
+            themed_animations,
         ))
         .id()
 }
 
 fn spawn_entrance(
     commands: &mut Commands,
-    asset_server: &AssetServer,
+    theme_assets: &ThemeAssets,
     rotation: Rotation,
     position: Pos,
 ) -> Entity {
@@ -409,11 +467,46 @@ fn spawn_entrance(
             Name::new("Wall"),
             Wall,
             Sprite {
-                image: asset_server.load("textures/walls/ghost_house_entrance.png"),
                 custom_size: Some(Vec2::splat(WALL_DIMENSION)),
-                ..default()
+                ..theme_assets.sprite("ghost_house_entrance")
             },
+            ThemedImage("ghost_house_entrance".to_string()),
             transform,
         ))
         .id()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::core::prelude::*;
+    use crate::spawn::enhance_map::enhance_ghost_house::{revive_offsets, slot_offsets};
+
+    #[test]
+    fn every_ghost_gets_its_own_slot() {
+        for rotation in [D0, D90, D180, D270] {
+            let tiles = (0..MAX_GHOSTS)
+                .map(|slot| slot_offsets(rotation, GhostSlot(slot)))
+                .flat_map(|(offsets_0, offsets_1, _)| [offsets_0, offsets_1])
+                .collect::<HashSet<_>>();
+
+            assert_eq!(tiles.len(), MAX_GHOSTS * 2);
+        }
+    }
+
+    #[test]
+    fn rotated_slots_keep_their_place_in_the_house() {
+        assert_eq!(slot_offsets(D90, GhostSlot(0)), ((5, 4), (5, 3), Up));
+        assert_eq!(slot_offsets(D90, GhostSlot(2)), ((2, 6), (2, 5), Left));
+        assert_eq!(slot_offsets(D180, GhostSlot(1)), ((4, 2), (3, 2), Down));
+        assert_eq!(slot_offsets(D270, GhostSlot(3)), ((2, 5), (2, 6), Right));
+    }
+
+    #[test]
+    fn ghosts_are_revived_in_the_center_row() {
+        assert_eq!(revive_offsets(D0, GhostSlot(0)), ((3, 2), (4, 2), Up));
+        assert_eq!(revive_offsets(D0, GhostSlot(5)), ((1, 2), (2, 2), Up));
+        assert_eq!(revive_offsets(D180, GhostSlot(9)), ((2, 2), (1, 2), Up));
+    }
+}
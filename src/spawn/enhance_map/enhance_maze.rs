@@ -1,5 +1,4 @@
 use crate::core::prelude::*;
-use crate::sprite_sheet::{SpriteSheet, SpriteSheets};
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 
@@ -18,11 +17,11 @@ type IsCorner = bool;
 
 fn enhance_maze(
     mut commands: Commands,
-    sprite_sheets: Res<SpriteSheets>,
+    theme_assets: ThemeAssets,
     mazes: Query<Entity, With<Maze>>,
     walls: Query<(Entity, &Tiles, &WallStyle), With<Wall>>,
 ) -> Result {
-    let wall_animations_map = create_animations(&sprite_sheets);
+    let wall_animations_map = create_animations();
 
     commands
         .entity(mazes.single()?)
@@ -30,51 +29,41 @@ fn enhance_maze(
 
     for (entity, tiles, style) in &walls {
         let transform = create_transform(tiles, &style.rotation);
-        let animations = wall_animations_map
+        let themed_animations = wall_animations_map
             .get(&(style.wall_type, style.is_corner))
             .unwrap()
             .clone();
+        let animations = theme_assets.animations(&themed_animations);
 
         commands.entity(entity).insert((
             Sprite {
-                image: animations.current().texture(),
                 custom_size: Some(Vec2::splat(WALL_DIMENSION)),
-                ..default()
+                ..animations.current().sprite()
             },
             transform,
             animations,
+            themed_animations,
         ));
     }
 
     Ok(())
 }
 
-fn create_animations(sprite_sheets: &SpriteSheets) -> HashMap<(WallType, IsCorner), Animations> {
+fn create_animations() -> HashMap<(WallType, IsCorner), ThemedAnimations> {
     [
-        (Outer, true, "textures/walls/outer_wall_corner"),
-        (Outer, false, "textures/walls/outer_wall"),
-        (Inner, true, "textures/walls/inner_wall_corner"),
-        (Inner, false, "textures/walls/inner_wall"),
+        (Outer, true, "outer_wall_corner"),
+        (Outer, false, "outer_wall"),
+        (Inner, true, "inner_wall_corner"),
+        (Inner, false, "inner_wall"),
     ]
     .into_iter()
-    .map(|(tp, is_corner, sheet_path)| {
-        (
-            (tp, is_corner),
-            create_wall_animations(sprite_sheets.get_sheet(sheet_path)),
-        )
-    })
+    .map(|(tp, is_corner, sprite)| ((tp, is_corner), create_wall_animations(sprite)))
     .collect()
 }
 
-fn create_wall_animations(sheet: &SpriteSheet) -> Animations {
-    Animations::new(
-        [
-            ("idle", Animation::from_texture(sheet.image_at(0))),
-            (
-                "blinking",
-                Animation::from_textures(0.5, true, sheet.images_at([0, 1])),
-            ),
-        ],
+fn create_wall_animations(sprite: &str) -> ThemedAnimations {
+    ThemedAnimations::new(
+        [("idle", sprite.to_string()), ("blinking", format!("{sprite}_blinking"))],
         "idle",
     )
 }
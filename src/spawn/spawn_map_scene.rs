@@ -9,7 +9,17 @@ impl Plugin for SpawnMapScenePlugin {
         app
             .add_systems(
                 OnEnter(SpawnMaze(SpawnMapScene)),
-                spawn_map_scene,
+                (
+                    (
+                        clean_up_game,
+                        despawn_previous_map
+                    ).chain().run_if(resource_exists::<LoadingMap>),
+                    spawn_map_scene,
+                ).chain(),
+            )
+            .add_systems(
+                Update,
+                rebuild_maze_when_map_changed.run_if(in_game),
             )
             .add_systems(
                 Startup,
@@ -19,25 +29,69 @@ impl Plugin for SpawnMapScenePlugin {
     }
 }
 
-/// The currently loading map scene entity
+/// The entity of the spawned map scene
 #[derive(Resource, Deref)]
 struct LoadingMap(Entity);
 
 fn spawn_map_scene(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    map_scene_path: Res<MapScenePath>,
 ) {
-    let entity = commands.spawn(DynamicSceneRoot(asset_server.load(MAP_SCENE_PATH))).id();
+    let entity = commands.spawn(DynamicSceneRoot(asset_server.load(map_scene_path.as_str()))).id();
 
     commands.insert_resource(LoadingMap(entity));
 }
 
+/// Despawn the maze before it gets rebuilt. The ghost house is not part of the map scene, so it is despawned on its own.
+fn despawn_previous_map(
+    mut commands: Commands,
+    loading_map: Res<LoadingMap>,
+    ghost_houses: Query<Entity, With<GhostHouse>>,
+) {
+    commands.entity(**loading_map).despawn();
+
+    for entity in &ghost_houses {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Rebuild the maze when the map scene file was changed, so changes to the map can be tried out without
+/// restarting. The current game ends and a new one starts on the changed map.
+///
+/// Changes are only noticed when the assets are watched, which is the case when the game runs with the dev feature.
+fn rebuild_maze_when_map_changed(
+    mut scene_events: MessageReader<AssetEvent<DynamicScene>>,
+    loading_map: Option<Res<LoadingMap>>,
+    map_scene_path: Res<MapScenePath>,
+    scene_roots: Query<&DynamicSceneRoot>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(map_scene) = loading_map.and_then(|map| scene_roots.get(**map).ok()) else {
+        return;
+    };
+
+    if scene_events.read().any(|event| event.is_modified(&map_scene.0)) {
+        info!("{} was changed, rebuilding the maze", **map_scene_path);
+        next_state.set(SpawnMaze(SpawnMapScene));
+    }
+}
+
 fn switch_state_when_map_spawned(
     mut commands: Commands,
 ) {
-    commands.add_observer(|_: On<SceneInstanceReady>, mut next_state: ResMut<NextState<GameState>>| {
-        // todo in the current bevy version at the time of writing (0.15.0), I don't really understand how to check if a
-        //  specific scene was spawned, only that some scene was spawned. But as I only have one, this should be fine
-        next_state.set(SpawnMaze(EnhanceMap));
+    commands.add_observer(|
+        ready: On<SceneInstanceReady>,
+        loading_map: Option<Res<LoadingMap>>,
+        current_state: Res<State<GameState>>,
+        mut next_state: ResMut<NextState<GameState>>,
+    | {
+        // Only the scene spawned last is the map, and it only continues the maze spawning once. When the map file
+        // changes during a game, the old scene instance gets updated, which must not interrupt the game.
+        let is_map = loading_map.is_some_and(|map| ready.entity == **map);
+
+        if is_map && *current_state.get() == SpawnMaze(SpawnMapScene) {
+            next_state.set(SpawnMaze(EnhanceMap));
+        }
     });
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
-use bevy::{asset::RenderAssetUsages, image::TextureFormatPixelInfo, prelude::*, render::render_resource::Extent3d, state::state::FreelyMutableState};
+use bevy::{prelude::*, state::state::FreelyMutableState};
 use bevy_common_assets::json::JsonAssetPlugin;
-use pad::{p, position::Position};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::core::animation::Animation;
 
 /// Plugin which will create sprite sheets from loaded aseprite json assets with their matching image
 /// assets. The sheets will be loaded when entering CreateState and afterwards, the plugin will switch to NextState.
@@ -53,14 +55,16 @@ fn create_sprite_sheets<S: States + FreelyMutableState>(
 ) -> impl Fn(
     Commands,
     Res<AssetServer>,
-    ResMut<Assets<Image>>,
+    Res<Assets<Image>>,
+    ResMut<Assets<TextureAtlasLayout>>,
     Res<Assets<AsepriteData>>,
     ResMut<NextState<S>>,
 ) {
-    move |mut commands, asset_server, mut images, aseprite_data, mut next_state| {
+    move |mut commands, asset_server, images, mut layouts, aseprite_data, mut next_state| {
         commands.insert_resource(create_sprite_sheets_from_aseprite_data(
             &asset_server,
-            &mut images,
+            &images,
+            &mut layouts,
             &aseprite_data,
         ));
         next_state.set(followup_state.clone())
@@ -69,7 +73,8 @@ fn create_sprite_sheets<S: States + FreelyMutableState>(
 
 fn create_sprite_sheets_from_aseprite_data(
     asset_server: &AssetServer,
-    images: &mut Assets<Image>,
+    images: &Assets<Image>,
+    layouts: &mut Assets<TextureAtlasLayout>,
     aseprite_data: &Assets<AsepriteData>,
 ) -> SpriteSheets {
     let paths_and_data = aseprite_data
@@ -104,73 +109,28 @@ fn create_sprite_sheets_from_aseprite_data(
                         .expect("the image path should have a file ending")
                         == path
                 })
-                .map(|(_, image)| (path, ad, image.clone()))
+                .map(|(asset_path, image)| (path, ad, asset_server.load::<Image>(asset_path), image.size()))
         })
         .collect::<Vec<_>>();
 
     SpriteSheets::new(
         paths_and_data
             .into_iter()
-            .map(|(path, aseprite_data, image)| {
+            .map(|(path, aseprite_data, image, size)| {
+                let mut layout = TextureAtlasLayout::new_empty(size);
+
+                for rect in aseprite_data.rect_iter() {
+                    layout.add_texture(rect);
+                }
+
                 (
                     path,
-                    SpriteSheet::new(
-                        split_image_by_rectangles(&image, aseprite_data.rect_iter())
-                            .into_iter()
-                            .map(|image| images.add(image)),
-                    ),
+                    SpriteSheet::new(image, layouts.add(layout), aseprite_data),
                 )
             }),
     )
 }
 
-/// Split a given image by the given iterator of rectangles and create sub images from it.
-pub fn split_image_by_rectangles<'a>(
-    image: &'a Image,
-    rectangles: impl IntoIterator<Item = Rect> + 'a,
-) -> impl IntoIterator<Item = Image> + 'a {
-    let dimension = image.texture_descriptor.dimension;
-    let format = image.texture_descriptor.format;
-    let sheet_width = image.texture_descriptor.size.width as usize
-        * format.pixel_size().expect("Could not retrieve pixel size");
-
-    rectangles.into_iter().map(move |rect| {
-        let size = Extent3d {
-            width: rect.width as u32,
-            height: rect.height as u32,
-            depth_or_array_layers: image.texture_descriptor.size.depth_or_array_layers,
-        };
-
-        let image_data = image.data.as_deref().expect("The image should be loaded");
-
-        let data = extract_rectangle(
-            image_data,
-            rect,
-            sheet_width,
-            format.pixel_size().expect("Could not retrieve pixel size"),
-        );
-        Image::new(size, dimension, data, format, RenderAssetUsages::default())
-    })
-}
-
-fn extract_rectangle(
-    data: &[u8],
-    rect: Rect,
-    data_width: usize,
-    pixel_width: usize,
-) -> Vec<u8> {
-    let mut extracted = Vec::with_capacity(rect.width * rect.height);
-    let start_index = data_width * rect.position.y as usize;
-
-    for y in 0..rect.height {
-        let start = start_index + y * data_width + rect.position.x as usize * pixel_width;
-        let end = start + rect.width * pixel_width;
-        data[start..end].iter().for_each(|val| extracted.push(*val))
-    }
-
-    extracted
-}
-
 /// Collection of all existing sprite sheets.
 /// As these sprite sheets aren't assets themself, they are stored in here instead of Assets.
 #[derive(Resource)]
@@ -189,109 +149,314 @@ impl SpriteSheets {
     ///
     /// The path should have no file ending, so if you have an asset "animation/my_animation.png" as a sheet
     /// and a "animation/my_animation.aseprite.json" aseprite file, you need to provide
-    /// "animation/my_animation" as parameter
+    /// "animation/my_animation" as parameter. Returns None if there is no such sheet.
     pub fn get_sheet(
         &self,
         path: &str,
-    ) -> &SpriteSheet {
-        self.path_sheet_map
-            .get(path)
-            .unwrap_or_else(|| panic!("sprite sheet {path} was not loaded!"))
+    ) -> Option<&SpriteSheet> {
+        self.path_sheet_map.get(path)
     }
 }
 
-/// Stores handles to image parts from a bigger sprite sheet image.
+/// A sprite sheet image and the layout of its frames, together with the
+/// timings and tags authored in aseprite.
 pub struct SpriteSheet {
-    pub textures: Vec<Handle<Image>>,
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    /// How long every frame is shown
+    pub durations: Vec<Duration>,
+    /// Named ranges of frames, each one representing an animation
+    pub tags: HashMap<String, FrameTag>,
 }
 
 impl SpriteSheet {
-    pub(crate) fn new(handles: impl IntoIterator<Item = Handle<Image>>) -> Self {
+    pub(crate) fn new(
+        image: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+        aseprite_data: &AsepriteData,
+    ) -> Self {
         Self {
-            textures: handles.into_iter().collect(),
+            image,
+            layout,
+            durations: aseprite_data.durations(),
+            tags: aseprite_data
+                .meta
+                .frame_tags
+                .iter()
+                .map(|tag| (tag.name.clone(), tag.clone()))
+                .collect(),
         }
     }
 
-    pub fn image_at(
+    /// Return the amount of frames in the sheet.
+    pub fn frame_count(&self) -> usize {
+        self.durations.len()
+    }
+
+    /// Return the indexes of the frames of the given tag in the order they are played,
+    /// or None if the sheet has no such tag.
+    pub fn tag_frames(
         &self,
-        index: usize,
-    ) -> Handle<Image> {
-        self.textures[index].clone()
+        tag: &str,
+    ) -> Option<Vec<usize>> {
+        self.tags.get(tag).map(FrameTag::frames)
     }
 
-    pub fn images_at(
+    /// Create an animation from the given frames, each one shown as long as authored.
+    pub fn animation(
         &self,
         indexes: impl IntoIterator<Item = usize>,
-    ) -> Vec<Handle<Image>> {
-        indexes
-            .into_iter()
-            .map(|i| self.textures[i].clone())
-            .collect()
+        repeating: bool,
+    ) -> Animation {
+        Animation::from_atlas(
+            repeating,
+            self.image.clone(),
+            self.layout.clone(),
+            indexes
+                .into_iter()
+                .map(|i| (i, self.durations[i])),
+        )
     }
-}
 
+    /// Create an animation from the given frames which takes the given time for one iteration.
+    pub fn evenly_timed_animation(
+        &self,
+        indexes: impl IntoIterator<Item = usize>,
+        duration_secs: f32,
+        repeating: bool,
+    ) -> Animation {
+        Animation::from_atlas_evenly(duration_secs, repeating, self.image.clone(), self.layout.clone(), indexes)
+    }
+
+    /// Create an animation which only shows the frame at the given index.
+    pub fn still(
+        &self,
+        index: usize,
+    ) -> Animation {
+        Animation::from_atlas_frame(
+            self.image.clone(),
+            TextureAtlas {
+                layout: self.layout.clone(),
+                index,
+            },
+        )
+    }
+}
 
 /// Represents the json data for a sprite sheet that can be generated when exporting a sheet.
 /// Used to load sheets from images using the data from the json file.
+///
+/// Both variants aseprite can export are supported: the frames as map from their names ("Hash")
+/// or as list ("Array").
 #[derive(Asset, Reflect, Deserialize, Clone)]
 #[reflect(opaque)]
 pub struct AsepriteData {
-    frames: HashMap<String, FrameValue>,
+    frames: AsepriteFrames,
+    #[serde(default)]
+    meta: AsepriteMeta,
 }
 
 impl AsepriteData {
-    /// Returns an rectangle iterator for this aseprite data.
-    /// All sub sprite names have a name like "<sheet name> <n>.aseprite", where
+    /// Return the frames in the order they appear in the sheet.
+    ///
+    /// In the hash variant, all sub sprite names have a name like "<sheet name> <n>.aseprite", where
     /// <sheet name> is the name of the sprite sheet and <n> is the index.
-    /// To return an ordered iterator, the entries must be sorted by key regarding length
+    /// To return them ordered, the entries must be sorted by key regarding length
     /// and name.
-    pub fn rect_iter(&self) -> impl IntoIterator<Item=Rect> + '_ {
+    fn ordered_frames(&self) -> Vec<&FrameValue> {
         use std::cmp::Ordering::*;
-        
-        let mut frames_vec = self.frames.iter().collect::<Vec<_>>();
-        frames_vec.sort_by(|(ka, _), (kb, _)| match ka.len().cmp(&kb.len()) {
-            Less => Less,
-            Greater => Greater,
-            Equal => ka.cmp(kb)
-        });
-
-        frames_vec
+
+        match &self.frames {
+            AsepriteFrames::Array(frames) => frames.iter().collect(),
+            AsepriteFrames::Hash(frames) => {
+                let mut frames_vec = frames.iter().collect::<Vec<_>>();
+                frames_vec.sort_by(|(ka, _), (kb, _)| match ka.len().cmp(&kb.len()) {
+                    Less => Less,
+                    Greater => Greater,
+                    Equal => ka.cmp(kb)
+                });
+
+                frames_vec.into_iter().map(|(_, fv)| fv).collect()
+            }
+        }
+    }
+
+    /// Returns an ordered rectangle iterator for this aseprite data.
+    pub fn rect_iter(&self) -> impl IntoIterator<Item=URect> + '_ {
+        self.ordered_frames()
             .into_iter()
-            .map(|(_, fv)| fv.frame)
-            .map(|f| Rect::new(p!(f.x, f.y), f.w, f.h))
+            .map(|fv| fv.frame)
+            .map(|f| URect::new(f.x, f.y, f.x + f.w, f.y + f.h))
     }
+
+    /// Return how long every frame is shown, in the order of the frames.
+    pub fn durations(&self) -> Vec<Duration> {
+        self.ordered_frames()
+            .into_iter()
+            .map(|fv| Duration::from_millis(fv.duration))
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum AsepriteFrames {
+    Hash(HashMap<String, FrameValue>),
+    Array(Vec<FrameValue>),
 }
 
 #[derive(Deserialize, Clone)]
 struct FrameValue {
     frame: Frame,
+    /// How long the frame is shown, in milliseconds
+    #[serde(default = "default_frame_duration")]
+    duration: u64,
+}
+
+/// The frame duration aseprite uses if nothing else is set
+fn default_frame_duration() -> u64 {
+    100
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteMeta {
+    #[serde(default)]
+    frame_tags: Vec<FrameTag>,
+}
+
+/// A named range of frames (both ends inclusive) and the direction they are played in.
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameTag {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+    #[serde(default)]
+    pub direction: TagDirection,
+}
+
+impl FrameTag {
+    /// Return the indexes of the frames in the order they are played.
+    ///
+    /// A ping-pong plays the frames forth and back, without repeating the frames at the turning points.
+    pub fn frames(&self) -> Vec<usize> {
+        let forward = (self.from..=self.to).collect::<Vec<_>>();
+        let backward = forward.iter().rev().copied().collect::<Vec<_>>();
+        let inner = |frames: &[usize]| frames[1..frames.len().saturating_sub(1).max(1)].to_vec();
+
+        match self.direction {
+            TagDirection::Forward => forward,
+            TagDirection::Reverse => backward,
+            TagDirection::Pingpong => [forward, inner(&backward)].concat(),
+            TagDirection::PingpongReverse => [backward, inner(&forward)].concat(),
+        }
+    }
+}
+
+#[derive(Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Pingpong,
+    PingpongReverse,
 }
 
 #[derive(Copy, Clone, Deserialize, Eq, PartialEq)]
 struct Frame {
-    x: usize,
-    y: usize,
-    w: usize,
-    h: usize,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Rect {
-    pub position: Position,
-    pub width: usize,
-    pub height: usize,
-}
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
 
-impl Rect {
-    pub fn new(
-        position: Position,
-        width: usize,
-        height: usize,
-    ) -> Self {
-        Self {
-            position,
-            width,
-            height,
+    use bevy::prelude::*;
+
+    use crate::sprite_sheet::{AsepriteData, SpriteSheet};
+
+    const HASH_JSON: &str = r#"{
+        "frames": {
+            "sheet 10.aseprite": { "frame": { "x": 160, "y": 0, "w": 16, "h": 16 }, "duration": 50 },
+            "sheet 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 200 },
+            "sheet 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 } }
         }
+    }"#;
+
+    const ARRAY_JSON: &str = r#"{
+        "frames": [
+            { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 80 },
+            { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 } },
+            { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 80 },
+            { "frame": { "x": 48, "y": 0, "w": 16, "h": 16 }, "duration": 80 }
+        ],
+        "meta": {
+            "frameTags": [
+                { "name": "forward", "from": 0, "to": 3 },
+                { "name": "reverse", "from": 1, "to": 3, "direction": "reverse" },
+                { "name": "pingpong", "from": 0, "to": 3, "direction": "pingpong" },
+                { "name": "pingpong_reverse", "from": 0, "to": 2, "direction": "pingpong_reverse" },
+                { "name": "single", "from": 2, "to": 2, "direction": "pingpong" }
+            ]
+        }
+    }"#;
+
+    fn parse(json: &str) -> AsepriteData {
+        serde_json::from_str(json).expect("the aseprite data should be valid")
+    }
+
+    fn sheet(json: &str) -> SpriteSheet {
+        SpriteSheet::new(Handle::default(), Handle::default(), &parse(json))
+    }
+
+    #[test]
+    fn hash_frames_are_ordered_by_their_index() {
+        let data = parse(HASH_JSON);
+        let rects = data.rect_iter().into_iter().collect::<Vec<_>>();
+
+        assert_eq!(rects, vec![
+            URect::new(16, 0, 32, 16),
+            URect::new(32, 0, 48, 16),
+            URect::new(160, 0, 176, 16),
+        ]);
+    }
+
+    #[test]
+    fn array_frames_keep_their_order() {
+        let data = parse(ARRAY_JSON);
+        let rects = data.rect_iter().into_iter().collect::<Vec<_>>();
+
+        assert_eq!(rects.len(), 4);
+        assert_eq!(rects[0], URect::new(0, 0, 16, 16));
+        assert_eq!(rects[3], URect::new(48, 0, 64, 16));
+    }
+
+    #[test]
+    fn frames_without_duration_are_shown_for_100_ms() {
+        let millis = |durations: Vec<Duration>| durations.into_iter().map(|d| d.as_millis()).collect::<Vec<_>>();
+
+        assert_eq!(millis(parse(HASH_JSON).durations()), vec![100, 200, 50]);
+        assert_eq!(millis(parse(ARRAY_JSON).durations()), vec![80, 100, 80, 80]);
+    }
+
+    #[test]
+    fn tags_are_played_in_their_direction() {
+        let sheet = sheet(ARRAY_JSON);
+
+        assert_eq!(sheet.tag_frames("forward"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(sheet.tag_frames("reverse"), Some(vec![3, 2, 1]));
+        assert_eq!(sheet.tag_frames("pingpong"), Some(vec![0, 1, 2, 3, 2, 1]));
+        assert_eq!(sheet.tag_frames("pingpong_reverse"), Some(vec![2, 1, 0, 1]));
+        assert_eq!(sheet.tag_frames("single"), Some(vec![2]));
+    }
+
+    #[test]
+    fn unknown_tags_have_no_frames() {
+        assert_eq!(sheet(ARRAY_JSON).tag_frames("missing"), None);
+        assert_eq!(sheet(HASH_JSON).tag_frames("forward"), None);
     }
 }